@@ -0,0 +1,41 @@
+﻿use apica_common::bytecodes::ApicaBuiltinFunctionBytecode;
+use apica_common::element::{Element, ElementModifier};
+use apica_common::values::error::ValueError;
+use apica_common::values::string::ValueString;
+use apica_common::values::value::Value;
+use crate::builtins::host::HostServices;
+use crate::builtins::registry::{argument_error, BuiltinFunction, BuiltinRegistry};
+use crate::systems::checker::TypeClass;
+use crate::utils::format;
+use crate::utils::rights::ApicaCapability;
+
+fn format(parameters: Vec<Element>, _host: &mut HostServices) -> Element {
+    match format::format_parameters(parameters) {
+        Ok(string) => Element::init(ElementModifier::None, Value::String(ValueString::init_with(string))),
+        Err(details) => Element::create_error(Value::Error(ValueError::init_with(
+            String::from("FormatError"),
+            Some(details)
+        ))),
+    }
+}
+
+fn throw(parameters: Vec<Element>, _host: &mut HostServices) -> Element {
+    if let Some(name_element) = parameters.first() && let Value::String(name) = name_element.get_value()
+        && let Some(error_name) = name.get_value() {
+        let details = parameters.get(1).map(format::element_to_string);
+        Element::create_error(Value::Error(ValueError::init_with(error_name.clone(), details)))
+    } else {
+        argument_error("Throw")
+    }
+}
+
+fn quit(_parameters: Vec<Element>, host: &mut HostServices) -> Element {
+    host.get_rights().quit_app();
+    Element::create_null()
+}
+
+pub fn register(registry: &mut BuiltinRegistry) {
+    registry.register(ApicaBuiltinFunctionBytecode::Format, BuiltinFunction::init("Format", (1, None), &[TypeClass::String], TypeClass::String, None, format));
+    registry.register(ApicaBuiltinFunctionBytecode::Throw, BuiltinFunction::init("Throw", (1, Some(2)), &[TypeClass::String], TypeClass::Unknown, None, throw));
+    registry.register(ApicaBuiltinFunctionBytecode::Quit, BuiltinFunction::init("Quit", (0, Some(0)), &[], TypeClass::Null, None, quit).with_capability(ApicaCapability::Quit));
+}
\ No newline at end of file
@@ -0,0 +1,40 @@
+﻿use apica_common::bytecodes::{ApicaBuiltinFunctionBytecode, ApicaTypeBytecode};
+use apica_common::element::Element;
+use apica_common::values::value::Value;
+use crate::builtins::host::HostServices;
+use crate::builtins::registry::{argument_error, BuiltinFunction, BuiltinRegistry};
+use crate::systems::checker::TypeClass;
+use crate::utils::rights::ApicaRight;
+
+fn list_apps(_: Vec<Element>, host: &mut HostServices) -> Element {
+    let (catalog, logger) = host.get_catalog_and_logger();
+    catalog.list_apps(logger)
+}
+
+fn get_app_count(_: Vec<Element>, host: &mut HostServices) -> Element {
+    let (catalog, logger) = host.get_catalog_and_logger();
+    catalog.get_app_count(logger)
+}
+
+fn get_app_info(parameters: Vec<Element>, host: &mut HostServices) -> Element {
+    let app = match parameters.first() {
+        Some(app) if matches!(app.get_value(), Value::String(_)) => app.get_value().clone(),
+        Some(app) => app.auto_convert(ApicaTypeBytecode::U32).get_value().clone(),
+        None => return argument_error("GetAppInfo"),
+    };
+
+    if let Some(field) = parameters.get(1) && let Value::String(field) = field.get_value() && let Some(field) = field.get_value() {
+        let (catalog, logger) = host.get_catalog_and_logger();
+        catalog.get_app_info(&app, field, logger)
+    } else {
+        argument_error("GetAppInfo")
+    }
+}
+
+pub fn register(registry: &mut BuiltinRegistry) {
+    const RIGHT: Option<ApicaRight> = Some(ApicaRight::MainMenuRight);
+
+    registry.register(ApicaBuiltinFunctionBytecode::ListApps, BuiltinFunction::init("ListApps", (0, Some(0)), &[], TypeClass::String, RIGHT, list_apps));
+    registry.register(ApicaBuiltinFunctionBytecode::GetAppCount, BuiltinFunction::init("GetAppCount", (0, Some(0)), &[], TypeClass::Numeric, RIGHT, get_app_count));
+    registry.register(ApicaBuiltinFunctionBytecode::GetAppInfo, BuiltinFunction::init("GetAppInfo", (2, Some(2)), &[TypeClass::Unknown, TypeClass::String], TypeClass::Unknown, RIGHT, get_app_info));
+}
\ No newline at end of file
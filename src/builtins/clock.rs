@@ -0,0 +1,41 @@
+﻿use apica_common::bytecodes::ApicaBuiltinFunctionBytecode;
+use apica_common::element::{Element, ElementModifier};
+use apica_common::values::f64::ValueF64;
+use apica_common::values::string::ValueString;
+use apica_common::values::u64::ValueU64;
+use apica_common::values::value::Value;
+use crate::builtins::host::HostServices;
+use crate::builtins::registry::{argument_error, BuiltinFunction, BuiltinRegistry};
+use crate::systems::checker::TypeClass;
+
+fn get_delta_time(_parameters: Vec<Element>, host: &mut HostServices) -> Element {
+    Element::init(ElementModifier::None, Value::F64(ValueF64::init_with(host.get_clock().get_delta_time())))
+}
+
+fn get_elapsed_time(_parameters: Vec<Element>, host: &mut HostServices) -> Element {
+    Element::init(ElementModifier::None, Value::F64(ValueF64::init_with(host.get_clock().get_elapsed_time())))
+}
+
+fn get_frame_count(_parameters: Vec<Element>, host: &mut HostServices) -> Element {
+    Element::init(ElementModifier::None, Value::U64(ValueU64::init_with(host.get_clock().get_frame_count())))
+}
+
+fn get_monotonic_millis(_parameters: Vec<Element>, host: &mut HostServices) -> Element {
+    Element::init(ElementModifier::None, Value::U64(ValueU64::init_with(host.get_clock().get_monotonic_millis())))
+}
+
+fn get_date_time(parameters: Vec<Element>, host: &mut HostServices) -> Element {
+    if let Some(date_time) = host.get_clock().get_date_time(parameters) {
+        Element::init(ElementModifier::None, Value::String(ValueString::init_with(date_time)))
+    } else {
+        argument_error("GetDateTime")
+    }
+}
+
+pub fn register(registry: &mut BuiltinRegistry) {
+    registry.register(ApicaBuiltinFunctionBytecode::GetDeltaTime, BuiltinFunction::init("GetDeltaTime", (0, Some(0)), &[], TypeClass::Numeric, None, get_delta_time));
+    registry.register(ApicaBuiltinFunctionBytecode::GetElapsedTime, BuiltinFunction::init("GetElapsedTime", (0, Some(0)), &[], TypeClass::Numeric, None, get_elapsed_time));
+    registry.register(ApicaBuiltinFunctionBytecode::GetFrameCount, BuiltinFunction::init("GetFrameCount", (0, Some(0)), &[], TypeClass::Numeric, None, get_frame_count));
+    registry.register(ApicaBuiltinFunctionBytecode::GetMonotonicMillis, BuiltinFunction::init("GetMonotonicMillis", (0, Some(0)), &[], TypeClass::Numeric, None, get_monotonic_millis));
+    registry.register(ApicaBuiltinFunctionBytecode::GetDateTime, BuiltinFunction::init("GetDateTime", (0, Some(1)), &[TypeClass::String], TypeClass::String, None, get_date_time));
+}
\ No newline at end of file
@@ -0,0 +1,82 @@
+﻿use apica_common::bytecodes::{ApicaBuiltinFunctionBytecode, ApicaTypeBytecode};
+use apica_common::element::Element;
+use apica_common::values::value::Value;
+use crate::builtins::host::HostServices;
+use crate::builtins::registry::{argument_error, BuiltinFunction, BuiltinRegistry};
+use crate::systems::checker::TypeClass;
+use crate::systems::files::FileMode;
+use crate::utils::rights::ApicaCapability;
+
+fn get_string(parameters: &[Element], index: usize) -> Option<String> {
+    if let Value::String(string) = parameters.get(index)?.get_value() && let Some(string) = string.get_value() {
+        return Some(string.clone());
+    }
+
+    None
+}
+
+fn get_handle(parameters: &[Element]) -> Option<u32> {
+    let handle = parameters.first()?.auto_convert(ApicaTypeBytecode::U32);
+    if let Value::U32(handle) = handle.get_value() {
+        return handle.get_value();
+    }
+
+    None
+}
+
+fn file_open(parameters: Vec<Element>, host: &mut HostServices) -> Element {
+    let mode = match parameters.get(1) {
+        Some(_) => get_string(&parameters, 1).and_then(|mode| FileMode::parse(&mode)),
+        None => Some(FileMode::Read),
+    };
+
+    match (get_string(&parameters, 0), mode) {
+        (Some(path), Some(mode)) => host.get_files().open(&path, mode),
+        _ => argument_error("FileOpen"),
+    }
+}
+
+fn file_read(parameters: Vec<Element>, host: &mut HostServices) -> Element {
+    match get_handle(&parameters) {
+        Some(handle) => host.get_files().read(handle),
+        None => argument_error("FileRead"),
+    }
+}
+
+fn file_write(parameters: Vec<Element>, host: &mut HostServices) -> Element {
+    match (get_handle(&parameters), get_string(&parameters, 1)) {
+        (Some(handle), Some(content)) => host.get_files().write(handle, &content),
+        _ => argument_error("FileWrite"),
+    }
+}
+
+fn file_close(parameters: Vec<Element>, host: &mut HostServices) -> Element {
+    match get_handle(&parameters) {
+        Some(handle) => host.get_files().close(handle),
+        None => argument_error("FileClose"),
+    }
+}
+
+fn file_list(parameters: Vec<Element>, host: &mut HostServices) -> Element {
+    let path = match parameters.first() {
+        Some(_) => get_string(&parameters, 0),
+        None => Some(String::from(".")),
+    };
+
+    match path {
+        Some(path) => host.get_files().list(&path),
+        None => argument_error("FileList"),
+    }
+}
+
+pub fn register(registry: &mut BuiltinRegistry) {
+    const STRING: TypeClass = TypeClass::String;
+    const NUMBER: TypeClass = TypeClass::Numeric;
+    const CAPABILITY: ApicaCapability = ApicaCapability::FileAccess;
+
+    registry.register(ApicaBuiltinFunctionBytecode::FileOpen, BuiltinFunction::init("FileOpen", (1, Some(2)), &[STRING, STRING], NUMBER, None, file_open).with_capability(CAPABILITY));
+    registry.register(ApicaBuiltinFunctionBytecode::FileRead, BuiltinFunction::init("FileRead", (1, Some(1)), &[NUMBER], STRING, None, file_read).with_capability(CAPABILITY));
+    registry.register(ApicaBuiltinFunctionBytecode::FileWrite, BuiltinFunction::init("FileWrite", (2, Some(2)), &[NUMBER, STRING], TypeClass::Null, None, file_write).with_capability(CAPABILITY));
+    registry.register(ApicaBuiltinFunctionBytecode::FileClose, BuiltinFunction::init("FileClose", (1, Some(1)), &[NUMBER], TypeClass::Null, None, file_close).with_capability(CAPABILITY));
+    registry.register(ApicaBuiltinFunctionBytecode::FileList, BuiltinFunction::init("FileList", (0, Some(1)), &[STRING], STRING, None, file_list).with_capability(CAPABILITY));
+}
\ No newline at end of file
@@ -0,0 +1,113 @@
+﻿use crate::systems::catalog::CatalogSystem;
+use crate::systems::clock::ClockSystem;
+use crate::systems::files::FileSystem;
+use crate::systems::inputs::InputsSystem;
+use crate::systems::launcher::LauncherSystem;
+use crate::systems::logger::LoggerSystem;
+use crate::systems::random::RandomSystem;
+use crate::systems::rights::RightSystem;
+use crate::systems::state::StateSystem;
+use crate::systems::storage::StorageSystem;
+use crate::systems::window::WindowSystem;
+
+pub struct AppServices {
+    state: StateSystem,
+    storage: StorageSystem,
+    files: FileSystem,
+    launcher: LauncherSystem,
+    catalog: CatalogSystem,
+}
+
+impl AppServices {
+    pub fn init(storage_quota: u64) -> AppServices {
+        AppServices {
+            state: StateSystem::init(),
+            storage: StorageSystem::init(storage_quota),
+            files: FileSystem::init(),
+            launcher: LauncherSystem::init(),
+            catalog: CatalogSystem::init(),
+        }
+    }
+
+    pub fn get_state(&mut self) -> &mut StateSystem {
+        &mut self.state
+    }
+
+    pub fn get_storage(&mut self) -> &mut StorageSystem {
+        &mut self.storage
+    }
+
+    pub fn get_files(&mut self) -> &mut FileSystem {
+        &mut self.files
+    }
+
+    pub fn get_launcher(&mut self) -> &mut LauncherSystem {
+        &mut self.launcher
+    }
+
+    pub fn get_catalog(&mut self) -> &mut CatalogSystem {
+        &mut self.catalog
+    }
+}
+
+pub struct HostServices<'a> {
+    logger: &'a mut LoggerSystem,
+    rights: &'a mut RightSystem,
+    window: &'a mut WindowSystem,
+    inputs: &'a mut InputsSystem,
+    clock: &'a ClockSystem,
+    random: &'a mut RandomSystem,
+    services: &'a mut AppServices,
+}
+
+impl<'a> HostServices<'a> {
+    pub fn init(logger: &'a mut LoggerSystem, rights: &'a mut RightSystem, window: &'a mut WindowSystem,
+                inputs: &'a mut InputsSystem, clock: &'a ClockSystem, random: &'a mut RandomSystem,
+                services: &'a mut AppServices) -> HostServices<'a> {
+        HostServices { logger, rights, window, inputs, clock, random, services }
+    }
+
+    pub fn get_logger(&mut self) -> &mut LoggerSystem {
+        self.logger
+    }
+
+    pub fn get_rights(&mut self) -> &mut RightSystem {
+        self.rights
+    }
+
+    pub fn get_window(&mut self) -> &mut WindowSystem {
+        self.window
+    }
+
+    pub fn get_inputs(&mut self) -> &mut InputsSystem {
+        self.inputs
+    }
+
+    pub fn get_clock(&self) -> &ClockSystem {
+        self.clock
+    }
+
+    pub fn get_random(&mut self) -> &mut RandomSystem {
+        self.random
+    }
+
+    pub fn get_state(&mut self) -> &mut StateSystem {
+        self.services.get_state()
+    }
+
+    pub fn get_storage(&mut self) -> &mut StorageSystem {
+        self.services.get_storage()
+    }
+
+    pub fn get_files(&mut self) -> &mut FileSystem {
+        self.services.get_files()
+    }
+
+    pub fn get_launcher(&mut self) -> &mut LauncherSystem {
+        self.services.get_launcher()
+    }
+
+    pub fn get_catalog_and_logger(&mut self) -> (&mut CatalogSystem, &mut LoggerSystem) {
+        (self.services.get_catalog(), self.logger)
+    }
+}
\ No newline at end of file
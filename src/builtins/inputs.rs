@@ -0,0 +1,33 @@
+﻿use apica_common::bytecodes::ApicaBuiltinFunctionBytecode;
+use apica_common::element::{Element, ElementModifier};
+use apica_common::values::bool::ValueBool;
+use apica_common::values::value::Value;
+use crate::builtins::host::HostServices;
+use crate::builtins::registry::{argument_error, BuiltinFunction, BuiltinRegistry};
+use crate::systems::checker::TypeClass;
+
+fn key_state(name: &str, state: Option<bool>) -> Element {
+    if let Some(state) = state {
+        Element::init(ElementModifier::None, Value::Bool(ValueBool::init_with(state)))
+    } else {
+        argument_error(name)
+    }
+}
+
+fn is_key_released(parameters: Vec<Element>, host: &mut HostServices) -> Element {
+    key_state("IsKeyReleased", host.get_inputs().is_key_released(parameters))
+}
+
+fn is_key_just_pressed(parameters: Vec<Element>, host: &mut HostServices) -> Element {
+    key_state("IsKeyJustPressed", host.get_inputs().is_key_just_pressed(parameters))
+}
+
+fn is_key_pressed(parameters: Vec<Element>, host: &mut HostServices) -> Element {
+    key_state("IsKeyPressed", host.get_inputs().is_key_pressed(parameters))
+}
+
+pub fn register(registry: &mut BuiltinRegistry) {
+    registry.register(ApicaBuiltinFunctionBytecode::IsKeyReleased, BuiltinFunction::init("IsKeyReleased", (1, Some(1)), &[TypeClass::Numeric], TypeClass::Bool, None, is_key_released));
+    registry.register(ApicaBuiltinFunctionBytecode::IsKeyJustPressed, BuiltinFunction::init("IsKeyJustPressed", (1, Some(1)), &[TypeClass::Numeric], TypeClass::Bool, None, is_key_just_pressed));
+    registry.register(ApicaBuiltinFunctionBytecode::IsKeyPressed, BuiltinFunction::init("IsKeyPressed", (1, Some(1)), &[TypeClass::Numeric], TypeClass::Bool, None, is_key_pressed));
+}
\ No newline at end of file
@@ -0,0 +1,61 @@
+﻿use apica_common::bytecodes::{ApicaBuiltinFunctionBytecode, ApicaTypeBytecode};
+use apica_common::element::Element;
+use apica_common::values::value::Value;
+use crate::builtins::host::HostServices;
+use crate::builtins::registry::{argument_error, BuiltinFunction, BuiltinRegistry};
+use crate::systems::checker::TypeClass;
+use crate::utils::rights::{ApicaCapability, ApicaRight};
+
+fn launch_app(mut parameters: Vec<Element>, host: &mut HostServices) -> Element {
+    if parameters.is_empty() {
+        return argument_error("LaunchApp");
+    }
+
+    let arguments = parameters.split_off(1);
+    if let Value::String(name) = parameters[0].get_value() && let Some(name) = name.get_value() {
+        host.get_launcher().request_launch(name, arguments)
+    } else {
+        argument_error("LaunchApp")
+    }
+}
+
+fn get_launch_args(parameters: Vec<Element>, host: &mut HostServices) -> Element {
+    let index = match parameters.first() {
+        Some(index) => index.auto_convert(ApicaTypeBytecode::U32),
+        None => return host.get_launcher().get_arguments(),
+    };
+
+    if let Value::U32(index) = index.get_value() && let Some(index) = index.get_value() {
+        host.get_launcher().get_argument(index)
+    } else {
+        argument_error("GetLaunchArgs")
+    }
+}
+
+fn get_launch_arg_count(_: Vec<Element>, host: &mut HostServices) -> Element {
+    host.get_launcher().get_argument_count()
+}
+
+fn set_app_result(parameters: Vec<Element>, host: &mut HostServices) -> Element {
+    match parameters.first() {
+        Some(result) => {
+            host.get_launcher().set_result(result);
+            Element::create_null()
+        },
+        None => argument_error("SetAppResult"),
+    }
+}
+
+fn get_app_result(_: Vec<Element>, host: &mut HostServices) -> Element {
+    host.get_launcher().get_result()
+}
+
+pub fn register(registry: &mut BuiltinRegistry) {
+    const ANY: TypeClass = TypeClass::Unknown;
+
+    registry.register(ApicaBuiltinFunctionBytecode::LaunchApp, BuiltinFunction::init("LaunchApp", (1, None), &[TypeClass::String], TypeClass::Null, Some(ApicaRight::MainMenuRight), launch_app).with_capability(ApicaCapability::LaunchApps));
+    registry.register(ApicaBuiltinFunctionBytecode::GetLaunchArgs, BuiltinFunction::init("GetLaunchArgs", (0, Some(1)), &[TypeClass::Numeric], ANY, None, get_launch_args));
+    registry.register(ApicaBuiltinFunctionBytecode::GetLaunchArgCount, BuiltinFunction::init("GetLaunchArgCount", (0, Some(0)), &[], TypeClass::Numeric, None, get_launch_arg_count));
+    registry.register(ApicaBuiltinFunctionBytecode::SetAppResult, BuiltinFunction::init("SetAppResult", (1, Some(1)), &[ANY], TypeClass::Null, None, set_app_result));
+    registry.register(ApicaBuiltinFunctionBytecode::GetAppResult, BuiltinFunction::init("GetAppResult", (0, Some(0)), &[], ANY, None, get_app_result));
+}
\ No newline at end of file
@@ -0,0 +1,82 @@
+﻿use apica_common::bytecodes::ApicaBuiltinFunctionBytecode;
+use apica_common::element::{Element, ElementModifier};
+use apica_common::values::error::ValueError;
+use apica_common::values::string::ValueString;
+use apica_common::values::value::Value;
+use crate::builtins::host::HostServices;
+use crate::builtins::registry::{BuiltinCallback, BuiltinFunction, BuiltinRegistry};
+use crate::systems::checker::TypeClass;
+use crate::systems::logger::LoggerSystem;
+use crate::utils::format;
+
+fn log_with(parameters: Vec<Element>, host: &mut HostServices, log: fn(&mut LoggerSystem, Vec<Element>)) -> Element {
+    let is_template = match parameters.first().map(Element::get_value) {
+        Some(Value::String(template)) => template.get_value().as_ref().is_some_and(|text| text.contains(['{', '}'])),
+        _ => false,
+    };
+
+    if !is_template {
+        log(host.get_logger(), parameters);
+        return Element::create_null();
+    }
+
+    match format::format_parameters(parameters) {
+        Ok(message) => {
+            log(host.get_logger(), vec![Element::init(ElementModifier::None, Value::String(ValueString::init_with(message)))]);
+            Element::create_null()
+        },
+        Err(details) => Element::create_error(Value::Error(ValueError::init_with(
+            String::from("FormatError"),
+            Some(details)
+        ))),
+    }
+}
+
+fn log_info(parameters: Vec<Element>, host: &mut HostServices) -> Element {
+    log_with(parameters, host, LoggerSystem::log_info)
+}
+
+fn logn_info(parameters: Vec<Element>, host: &mut HostServices) -> Element {
+    log_with(parameters, host, LoggerSystem::logn_info)
+}
+
+fn log_success(parameters: Vec<Element>, host: &mut HostServices) -> Element {
+    log_with(parameters, host, LoggerSystem::log_success)
+}
+
+fn logn_success(parameters: Vec<Element>, host: &mut HostServices) -> Element {
+    log_with(parameters, host, LoggerSystem::logn_success)
+}
+
+fn log_warning(parameters: Vec<Element>, host: &mut HostServices) -> Element {
+    log_with(parameters, host, LoggerSystem::log_warning)
+}
+
+fn logn_warning(parameters: Vec<Element>, host: &mut HostServices) -> Element {
+    log_with(parameters, host, LoggerSystem::logn_warning)
+}
+
+fn log_error(parameters: Vec<Element>, host: &mut HostServices) -> Element {
+    log_with(parameters, host, LoggerSystem::log_error)
+}
+
+fn logn_error(parameters: Vec<Element>, host: &mut HostServices) -> Element {
+    log_with(parameters, host, LoggerSystem::logn_error)
+}
+
+pub fn register(registry: &mut BuiltinRegistry) {
+    let functions: [(ApicaBuiltinFunctionBytecode, &'static str, BuiltinCallback); 8] = [
+        (ApicaBuiltinFunctionBytecode::LogInfo, "LogInfo", log_info),
+        (ApicaBuiltinFunctionBytecode::LognInfo, "LognInfo", logn_info),
+        (ApicaBuiltinFunctionBytecode::LogSuccess, "LogSuccess", log_success),
+        (ApicaBuiltinFunctionBytecode::LognSuccess, "LognSuccess", logn_success),
+        (ApicaBuiltinFunctionBytecode::LogWarning, "LogWarning", log_warning),
+        (ApicaBuiltinFunctionBytecode::LognWarning, "LognWarning", logn_warning),
+        (ApicaBuiltinFunctionBytecode::LogError, "LogError", log_error),
+        (ApicaBuiltinFunctionBytecode::LognError, "LognError", logn_error),
+    ];
+
+    for (function, name, callback) in functions {
+        registry.register(function, BuiltinFunction::init(name, (0, None), &[], TypeClass::Null, None, callback));
+    }
+}
\ No newline at end of file
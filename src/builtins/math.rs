@@ -0,0 +1,68 @@
+﻿use apica_common::bytecodes::ApicaBuiltinFunctionBytecode;
+use apica_common::element::Element;
+use crate::builtins::host::HostServices;
+use crate::builtins::registry::{BuiltinHandler, BuiltinRegistry};
+use crate::systems::checker::TypeClass;
+use crate::utils::math;
+use crate::utils::rights::ApicaRight;
+
+const NUMBERS: [TypeClass; 3] = [TypeClass::Numeric; 3];
+
+struct MathFunction {
+    function: ApicaBuiltinFunctionBytecode,
+    name: &'static str,
+    arity: usize,
+}
+
+impl BuiltinHandler for MathFunction {
+    fn get_name(&self) -> &str {
+        self.name
+    }
+
+    fn get_arity(&self) -> (usize, Option<usize>) {
+        (self.arity, Some(self.arity))
+    }
+
+    fn get_parameter_types(&self) -> &[TypeClass] {
+        &NUMBERS[..self.arity]
+    }
+
+    fn get_result_type(&self) -> TypeClass {
+        TypeClass::Numeric
+    }
+
+    fn get_required_right(&self) -> Option<ApicaRight> {
+        None
+    }
+
+    fn call(&self, parameters: Vec<Element>, _host: &mut HostServices) -> Element {
+        math::evaluate(&self.function, parameters)
+    }
+}
+
+pub fn register(registry: &mut BuiltinRegistry) {
+    let functions = [
+        (ApicaBuiltinFunctionBytecode::Abs, "Abs", 1),
+        (ApicaBuiltinFunctionBytecode::Min, "Min", 2),
+        (ApicaBuiltinFunctionBytecode::Max, "Max", 2),
+        (ApicaBuiltinFunctionBytecode::Clamp, "Clamp", 3),
+        (ApicaBuiltinFunctionBytecode::Pow, "Pow", 2),
+        (ApicaBuiltinFunctionBytecode::Sqrt, "Sqrt", 1),
+        (ApicaBuiltinFunctionBytecode::Floor, "Floor", 1),
+        (ApicaBuiltinFunctionBytecode::Ceil, "Ceil", 1),
+        (ApicaBuiltinFunctionBytecode::Round, "Round", 1),
+        (ApicaBuiltinFunctionBytecode::Sin, "Sin", 1),
+        (ApicaBuiltinFunctionBytecode::Cos, "Cos", 1),
+        (ApicaBuiltinFunctionBytecode::Tan, "Tan", 1),
+        (ApicaBuiltinFunctionBytecode::Asin, "Asin", 1),
+        (ApicaBuiltinFunctionBytecode::Acos, "Acos", 1),
+        (ApicaBuiltinFunctionBytecode::Atan, "Atan", 1),
+        (ApicaBuiltinFunctionBytecode::Atan2, "Atan2", 2),
+        (ApicaBuiltinFunctionBytecode::Lerp, "Lerp", 3),
+        (ApicaBuiltinFunctionBytecode::Sign, "Sign", 1),
+    ];
+
+    for (function, name, arity) in functions {
+        registry.register(function, Box::new(MathFunction { function, name, arity }));
+    }
+}
\ No newline at end of file
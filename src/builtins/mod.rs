@@ -0,0 +1,14 @@
+﻿pub mod host;
+pub mod registry;
+pub mod base;
+pub mod log;
+pub mod window;
+pub mod inputs;
+pub mod clock;
+pub mod random;
+pub mod math;
+pub mod state;
+pub mod storage;
+pub mod files;
+pub mod launcher;
+pub mod catalog;
\ No newline at end of file
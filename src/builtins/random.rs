@@ -0,0 +1,45 @@
+﻿use apica_common::bytecodes::ApicaBuiltinFunctionBytecode;
+use apica_common::element::{Element, ElementModifier};
+use apica_common::values::f64::ValueF64;
+use apica_common::values::i64::ValueI64;
+use apica_common::values::value::Value;
+use crate::builtins::host::HostServices;
+use crate::builtins::registry::{argument_error, BuiltinFunction, BuiltinRegistry};
+use crate::systems::checker::TypeClass;
+
+fn random_seed(parameters: Vec<Element>, host: &mut HostServices) -> Element {
+    if host.get_random().random_seed(parameters) {
+        Element::create_null()
+    } else {
+        argument_error("RandomSeed")
+    }
+}
+
+fn random_int(parameters: Vec<Element>, host: &mut HostServices) -> Element {
+    if let Some(value) = host.get_random().random_int(parameters) {
+        Element::init(ElementModifier::None, Value::I64(ValueI64::init_with(value)))
+    } else {
+        argument_error("RandomInt")
+    }
+}
+
+fn random_float(parameters: Vec<Element>, host: &mut HostServices) -> Element {
+    if let Some(value) = host.get_random().random_float(parameters) {
+        Element::init(ElementModifier::None, Value::F64(ValueF64::init_with(value)))
+    } else {
+        argument_error("RandomFloat")
+    }
+}
+
+fn random_choice(parameters: Vec<Element>, host: &mut HostServices) -> Element {
+    host.get_random().random_choice(parameters).unwrap_or_else(|| argument_error("RandomChoice"))
+}
+
+pub fn register(registry: &mut BuiltinRegistry) {
+    const NUMBER: TypeClass = TypeClass::Numeric;
+
+    registry.register(ApicaBuiltinFunctionBytecode::RandomSeed, BuiltinFunction::init("RandomSeed", (1, Some(1)), &[NUMBER], TypeClass::Null, None, random_seed));
+    registry.register(ApicaBuiltinFunctionBytecode::RandomInt, BuiltinFunction::init("RandomInt", (2, Some(2)), &[NUMBER, NUMBER], NUMBER, None, random_int));
+    registry.register(ApicaBuiltinFunctionBytecode::RandomFloat, BuiltinFunction::init("RandomFloat", (0, Some(2)), &[NUMBER, NUMBER], NUMBER, None, random_float));
+    registry.register(ApicaBuiltinFunctionBytecode::RandomChoice, BuiltinFunction::init("RandomChoice", (1, None), &[], TypeClass::Unknown, None, random_choice));
+}
\ No newline at end of file
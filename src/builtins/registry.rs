@@ -0,0 +1,141 @@
+﻿use std::collections::HashMap;
+use apica_common::bytecodes::ApicaBuiltinFunctionBytecode;
+use apica_common::element::Element;
+use apica_common::values::error::ValueError;
+use apica_common::values::value::Value;
+use crate::builtins::host::HostServices;
+use crate::systems::checker::TypeClass;
+use crate::utils::rights::{ApicaCapability, ApicaRight};
+
+pub trait BuiltinHandler {
+    fn get_name(&self) -> &str;
+
+    fn get_arity(&self) -> (usize, Option<usize>);
+
+    fn get_parameter_types(&self) -> &[TypeClass];
+
+    fn get_result_type(&self) -> TypeClass;
+
+    fn get_required_right(&self) -> Option<ApicaRight>;
+
+    fn get_required_capability(&self) -> Option<ApicaCapability> {
+        None
+    }
+
+    fn call(&self, parameters: Vec<Element>, host: &mut HostServices) -> Element;
+}
+
+pub type BuiltinCallback = fn(Vec<Element>, &mut HostServices) -> Element;
+
+pub struct BuiltinFunction {
+    name: &'static str,
+    arity: (usize, Option<usize>),
+    parameter_types: &'static [TypeClass],
+    result_type: TypeClass,
+    required_right: Option<ApicaRight>,
+    required_capability: Option<ApicaCapability>,
+    callback: BuiltinCallback,
+}
+
+impl BuiltinFunction {
+    pub fn init(name: &'static str, arity: (usize, Option<usize>), parameter_types: &'static [TypeClass],
+                result_type: TypeClass, required_right: Option<ApicaRight>, callback: BuiltinCallback) -> Box<BuiltinFunction> {
+        Box::new(BuiltinFunction { name, arity, parameter_types, result_type, required_right, required_capability: None, callback })
+    }
+
+    pub fn with_capability(mut self: Box<Self>, capability: ApicaCapability) -> Box<BuiltinFunction> {
+        self.required_capability = Some(capability);
+        self
+    }
+}
+
+impl BuiltinHandler for BuiltinFunction {
+    fn get_name(&self) -> &str {
+        self.name
+    }
+
+    fn get_arity(&self) -> (usize, Option<usize>) {
+        self.arity
+    }
+
+    fn get_parameter_types(&self) -> &[TypeClass] {
+        self.parameter_types
+    }
+
+    fn get_result_type(&self) -> TypeClass {
+        self.result_type
+    }
+
+    fn get_required_right(&self) -> Option<ApicaRight> {
+        self.required_right
+    }
+
+    fn get_required_capability(&self) -> Option<ApicaCapability> {
+        self.required_capability
+    }
+
+    fn call(&self, parameters: Vec<Element>, host: &mut HostServices) -> Element {
+        (self.callback)(parameters, host)
+    }
+}
+
+pub struct BuiltinRegistry {
+    handlers: Vec<Box<dyn BuiltinHandler>>,
+    bytecodes: HashMap<u64, usize>,
+    names: HashMap<String, usize>,
+}
+
+impl BuiltinRegistry {
+    pub fn init() -> BuiltinRegistry {
+        let mut registry = BuiltinRegistry { handlers: vec![], bytecodes: HashMap::new(), names: HashMap::new() };
+        crate::builtins::base::register(&mut registry);
+        crate::builtins::log::register(&mut registry);
+        crate::builtins::window::register(&mut registry);
+        crate::builtins::inputs::register(&mut registry);
+        crate::builtins::clock::register(&mut registry);
+        crate::builtins::random::register(&mut registry);
+        crate::builtins::math::register(&mut registry);
+        crate::builtins::state::register(&mut registry);
+        crate::builtins::storage::register(&mut registry);
+        crate::builtins::files::register(&mut registry);
+        crate::builtins::launcher::register(&mut registry);
+        crate::builtins::catalog::register(&mut registry);
+        registry
+    }
+
+    pub fn register(&mut self, function: ApicaBuiltinFunctionBytecode, handler: Box<dyn BuiltinHandler>) {
+        self.names.insert(handler.get_name().to_string(), self.handlers.len());
+        self.bytecodes.insert(function as u64, self.handlers.len());
+        self.handlers.push(handler);
+    }
+
+    pub fn register_named(&mut self, handler: Box<dyn BuiltinHandler>) -> bool {
+        if self.names.contains_key(handler.get_name()) {
+            return false;
+        }
+
+        self.names.insert(handler.get_name().to_string(), self.handlers.len());
+        self.handlers.push(handler);
+        true
+    }
+
+    pub fn get(&self, function: &ApicaBuiltinFunctionBytecode) -> Option<&dyn BuiltinHandler> {
+        self.bytecodes.get(&(*function as u64)).map(|index| self.handlers[*index].as_ref())
+    }
+
+    pub fn get_named(&self, name: &str) -> Option<&dyn BuiltinHandler> {
+        self.names.get(name).map(|index| self.handlers[*index].as_ref())
+    }
+}
+
+pub fn accepts_arity(handler: &dyn BuiltinHandler, count: usize) -> bool {
+    let (min_arity, max_arity) = handler.get_arity();
+    count >= min_arity && max_arity.is_none_or(|max_arity| count <= max_arity)
+}
+
+pub fn argument_error(name: &str) -> Element {
+    Element::create_error(Value::Error(ValueError::init_with(
+        String::from("ArgumentError"),
+        Some(format!("Incorrect arguments passed to the function `{name}`"))
+    )))
+}
\ No newline at end of file
@@ -0,0 +1,42 @@
+﻿use apica_common::bytecodes::{ApicaBuiltinFunctionBytecode, ApicaTypeBytecode};
+use apica_common::element::Element;
+use apica_common::values::value::Value;
+use crate::builtins::host::HostServices;
+use crate::builtins::registry::{argument_error, BuiltinFunction, BuiltinRegistry};
+use crate::systems::checker::TypeClass;
+use crate::systems::state::StateRequest;
+use crate::utils::rights::{ApicaCapability, ApicaRight};
+
+fn get_slot(parameters: &[Element]) -> Option<u32> {
+    let slot = parameters.first()?.auto_convert(ApicaTypeBytecode::U32);
+    if let Value::U32(slot) = slot.get_value() {
+        return slot.get_value();
+    }
+
+    None
+}
+
+fn save_state(parameters: Vec<Element>, host: &mut HostServices) -> Element {
+    match get_slot(&parameters) {
+        Some(slot) => {
+            host.get_state().request(StateRequest::Save(slot));
+            Element::create_null()
+        },
+        None => argument_error("SaveState"),
+    }
+}
+
+fn load_state(parameters: Vec<Element>, host: &mut HostServices) -> Element {
+    match get_slot(&parameters) {
+        Some(slot) => {
+            host.get_state().request(StateRequest::Load(slot));
+            Element::create_null()
+        },
+        None => argument_error("LoadState"),
+    }
+}
+
+pub fn register(registry: &mut BuiltinRegistry) {
+    registry.register(ApicaBuiltinFunctionBytecode::SaveState, BuiltinFunction::init("SaveState", (1, Some(1)), &[TypeClass::Numeric], TypeClass::Null, Some(ApicaRight::AppRight), save_state).with_capability(ApicaCapability::Storage));
+    registry.register(ApicaBuiltinFunctionBytecode::LoadState, BuiltinFunction::init("LoadState", (1, Some(1)), &[TypeClass::Numeric], TypeClass::Null, Some(ApicaRight::AppRight), load_state).with_capability(ApicaCapability::Storage));
+}
\ No newline at end of file
@@ -0,0 +1,50 @@
+﻿use apica_common::bytecodes::ApicaBuiltinFunctionBytecode;
+use apica_common::element::Element;
+use apica_common::values::value::Value;
+use crate::builtins::host::HostServices;
+use crate::builtins::registry::{argument_error, BuiltinFunction, BuiltinRegistry};
+use crate::systems::checker::TypeClass;
+use crate::utils::rights::ApicaCapability;
+
+fn get_key(parameters: &[Element]) -> Option<String> {
+    if let Value::String(key) = parameters.first()?.get_value() && let Some(key) = key.get_value() {
+        return Some(key.clone());
+    }
+
+    None
+}
+
+fn store_set(parameters: Vec<Element>, host: &mut HostServices) -> Element {
+    match (get_key(&parameters), parameters.get(1)) {
+        (Some(key), Some(value)) => host.get_storage().set(&key, value.get_value()),
+        _ => argument_error("StoreSet"),
+    }
+}
+
+fn store_get(parameters: Vec<Element>, host: &mut HostServices) -> Element {
+    match get_key(&parameters) {
+        Some(key) => host.get_storage().get(&key, parameters.get(1)),
+        None => argument_error("StoreGet"),
+    }
+}
+
+fn store_delete(parameters: Vec<Element>, host: &mut HostServices) -> Element {
+    match get_key(&parameters) {
+        Some(key) => host.get_storage().delete(&key),
+        None => argument_error("StoreDelete"),
+    }
+}
+
+fn store_keys(_: Vec<Element>, host: &mut HostServices) -> Element {
+    host.get_storage().keys()
+}
+
+pub fn register(registry: &mut BuiltinRegistry) {
+    const STRING: TypeClass = TypeClass::String;
+    const ANY: TypeClass = TypeClass::Unknown;
+
+    registry.register(ApicaBuiltinFunctionBytecode::StoreSet, BuiltinFunction::init("StoreSet", (2, Some(2)), &[STRING, ANY], TypeClass::Null, None, store_set).with_capability(ApicaCapability::Storage));
+    registry.register(ApicaBuiltinFunctionBytecode::StoreGet, BuiltinFunction::init("StoreGet", (1, Some(2)), &[STRING, ANY], ANY, None, store_get).with_capability(ApicaCapability::Storage));
+    registry.register(ApicaBuiltinFunctionBytecode::StoreDelete, BuiltinFunction::init("StoreDelete", (1, Some(1)), &[STRING], TypeClass::Bool, None, store_delete).with_capability(ApicaCapability::Storage));
+    registry.register(ApicaBuiltinFunctionBytecode::StoreKeys, BuiltinFunction::init("StoreKeys", (0, Some(0)), &[], STRING, None, store_keys).with_capability(ApicaCapability::Storage));
+}
\ No newline at end of file
@@ -0,0 +1,27 @@
+﻿use apica_common::bytecodes::ApicaBuiltinFunctionBytecode;
+use apica_common::element::Element;
+use crate::builtins::host::HostServices;
+use crate::builtins::registry::{argument_error, BuiltinFunction, BuiltinRegistry};
+use crate::systems::checker::TypeClass;
+use crate::utils::rights::ApicaCapability;
+
+fn set_title(parameters: Vec<Element>, host: &mut HostServices) -> Element {
+    if host.get_window().set_title(parameters) {
+        Element::create_null()
+    } else {
+        argument_error("SetTitle")
+    }
+}
+
+fn set_resizable(parameters: Vec<Element>, host: &mut HostServices) -> Element {
+    if host.get_window().set_resizable(parameters) {
+        Element::create_null()
+    } else {
+        argument_error("SetResizable")
+    }
+}
+
+pub fn register(registry: &mut BuiltinRegistry) {
+    registry.register(ApicaBuiltinFunctionBytecode::SetTitle, BuiltinFunction::init("SetTitle", (1, Some(1)), &[], TypeClass::Null, None, set_title).with_capability(ApicaCapability::Window));
+    registry.register(ApicaBuiltinFunctionBytecode::SetResizable, BuiltinFunction::init("SetResizable", (1, Some(1)), &[TypeClass::Bool], TypeClass::Null, None, set_resizable).with_capability(ApicaCapability::Window));
+}
\ No newline at end of file
@@ -1,11 +1,20 @@
 use winit::event_loop::{ControlFlow, EventLoop};
 use crate::systems::apica::ApicaSystem;
 
+pub mod builtins;
 pub mod nodes;
+pub mod plugins;
 pub mod systems;
 pub mod utils;
 
 fn main() {
+    let arguments: Vec<String> = std::env::args().collect();
+    if arguments.len() >= 3 && arguments[1] == "--bench-engines" {
+        let iterations = arguments.get(3).and_then(|value| value.parse().ok()).unwrap_or(1000);
+        ApicaSystem::benchmark_engines(&arguments[2], iterations);
+        return;
+    }
+
     let event_loop = EventLoop::new().unwrap();
     event_loop.set_control_flow(ControlFlow::Poll);
 
@@ -13,8 +13,20 @@ impl NodeWhile {
     pub fn get_condition(&self) -> &Node {
         &self.condition
     }
+
+    pub fn get_condition_mut(&mut self) -> &mut Node {
+        &mut self.condition
+    }
     
     pub fn get_body(&self) -> &Node {
         &self.body
     }
+
+    pub fn get_body_mut(&mut self) -> &mut Node {
+        &mut self.body
+    }
+
+    pub fn into_parts(self) -> (Node, Node) {
+        (self.condition, self.body)
+    }
 }
\ No newline at end of file
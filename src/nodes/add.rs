@@ -13,8 +13,20 @@ impl NodeAdd {
     pub fn get_left(&self) -> &Node {
         &self.left
     }
+
+    pub fn get_left_mut(&mut self) -> &mut Node {
+        &mut self.left
+    }
     
     pub fn get_right(&self) -> &Node {
         &self.right
     }
+
+    pub fn get_right_mut(&mut self) -> &mut Node {
+        &mut self.right
+    }
+
+    pub fn into_parts(self) -> (Node, Node) {
+        (self.left, self.right)
+    }
 }
\ No newline at end of file
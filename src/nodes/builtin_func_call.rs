@@ -18,4 +18,12 @@ impl NodeBuiltinFuncCall {
     pub fn get_parameters(&self) -> &Vec<Node> {
         &self.parameters
     }
+
+    pub fn get_parameters_mut(&mut self) -> &mut Vec<Node> {
+        &mut self.parameters
+    }
+
+    pub fn into_parts(self) -> (ApicaBuiltinFunctionBytecode, Vec<Node>) {
+        (self.func_bytecode, self.parameters)
+    }
 }
\ No newline at end of file
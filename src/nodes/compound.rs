@@ -12,4 +12,12 @@ impl NodeCompound {
     pub fn get_nodes(&self) -> &Vec<Node> {
         &self.nodes
     }
+
+    pub fn get_nodes_mut(&mut self) -> &mut Vec<Node> {
+        &mut self.nodes
+    }
+
+    pub fn into_nodes(self) -> Vec<Node> {
+        self.nodes
+    }
 }
\ No newline at end of file
@@ -1,15 +1,17 @@
 ﻿use apica_common::bytecodes::ApicaTypeBytecode;
 use crate::nodes::node::Node;
+use crate::nodes::slot::Slot;
 
 pub struct NodeConstDecl {
     name: String,
     value_kind: ApicaTypeBytecode,
     expression: Node,
+    slot: Option<Slot>,
 }
 
 impl NodeConstDecl {
     pub fn init(name: String, value_kind: ApicaTypeBytecode, expression: Node) -> NodeConstDecl {
-        NodeConstDecl { name, value_kind, expression }
+        NodeConstDecl { name, value_kind, expression, slot: None }
     }
     
     pub fn get_name(&self) -> &String {
@@ -23,4 +25,20 @@ impl NodeConstDecl {
     pub fn get_expression(&self) -> &Node {
         &self.expression
     }
+
+    pub fn get_expression_mut(&mut self) -> &mut Node {
+        &mut self.expression
+    }
+
+    pub fn get_slot(&self) -> Option<Slot> {
+        self.slot
+    }
+
+    pub fn set_slot(&mut self, slot: Slot) {
+        self.slot = Some(slot);
+    }
+
+    pub fn into_parts(self) -> (String, ApicaTypeBytecode, Node) {
+        (self.name, self.value_kind, self.expression)
+    }
 }
\ No newline at end of file
@@ -14,8 +14,16 @@ impl NodeConvert {
     pub fn get_left(&self) -> &Node {
         &self.left
     }
+
+    pub fn get_left_mut(&mut self) -> &mut Node {
+        &mut self.left
+    }
     
     pub fn get_right(&self) -> ApicaTypeBytecode {
         self.right
     }
+
+    pub fn into_parts(self) -> (Node, ApicaTypeBytecode) {
+        (self.left, self.right)
+    }
 }
\ No newline at end of file
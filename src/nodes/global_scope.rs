@@ -12,4 +12,12 @@ impl NodeGlobalScope {
     pub fn get_statement(&self) -> &Node {
         &self.statement
     }
+
+    pub fn get_statement_mut(&mut self) -> &mut Node {
+        &mut self.statement
+    }
+
+    pub fn into_statement(self) -> Node {
+        self.statement
+    }
 }
\ No newline at end of file
@@ -14,12 +14,28 @@ impl NodeIfElse {
     pub fn get_condition(&self) -> &Node {
         &self.condition
     }
+
+    pub fn get_condition_mut(&mut self) -> &mut Node {
+        &mut self.condition
+    }
     
     pub fn get_if_body(&self) -> &Node {
         &self.if_body
     }
+
+    pub fn get_if_body_mut(&mut self) -> &mut Node {
+        &mut self.if_body
+    }
     
     pub fn get_else_body(&self) -> &Node {
         &self.else_body
     }
+
+    pub fn get_else_body_mut(&mut self) -> &mut Node {
+        &mut self.else_body
+    }
+
+    pub fn into_parts(self) -> (Node, Node, Node) {
+        (self.condition, self.if_body, self.else_body)
+    }
 }
\ No newline at end of file
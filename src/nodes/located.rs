@@ -0,0 +1,49 @@
+﻿use std::fmt::{Display, Formatter};
+use std::rc::Rc;
+use crate::nodes::node::Node;
+
+#[derive(Clone)]
+pub struct SourceLocation {
+    file: Rc<str>,
+    line: u32,
+    column: u32,
+}
+
+impl SourceLocation {
+    pub fn init(file: Rc<str>, line: u32, column: u32) -> SourceLocation {
+        SourceLocation { file, line, column }
+    }
+}
+
+impl Display for SourceLocation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}:{}", self.file, self.line, self.column)
+    }
+}
+
+pub struct NodeLocated {
+    location: SourceLocation,
+    node: Node,
+}
+
+impl NodeLocated {
+    pub fn init(location: SourceLocation, node: Node) -> NodeLocated {
+        NodeLocated { location, node }
+    }
+
+    pub fn get_location(&self) -> &SourceLocation {
+        &self.location
+    }
+
+    pub fn get_node(&self) -> &Node {
+        &self.node
+    }
+
+    pub fn get_node_mut(&mut self) -> &mut Node {
+        &mut self.node
+    }
+
+    pub fn into_parts(self) -> (SourceLocation, Node) {
+        (self.location, self.node)
+    }
+}
\ No newline at end of file
@@ -17,4 +17,8 @@ pub mod _continue;
 pub mod not;
 pub mod blank_return;
 pub mod add;
-pub mod convert;
\ No newline at end of file
+pub mod convert;
+pub mod try_catch;
+pub mod located;
+pub mod slot;
+pub mod named_func_call;
\ No newline at end of file
@@ -0,0 +1,28 @@
+﻿use crate::nodes::node::Node;
+
+pub struct NodeNamedFuncCall {
+    name: String,
+    parameters: Vec<Node>,
+}
+
+impl NodeNamedFuncCall {
+    pub fn init(name: String, parameters: Vec<Node>) -> NodeNamedFuncCall {
+        NodeNamedFuncCall { name, parameters }
+    }
+
+    pub fn get_name(&self) -> &String {
+        &self.name
+    }
+
+    pub fn get_parameters(&self) -> &Vec<Node> {
+        &self.parameters
+    }
+
+    pub fn get_parameters_mut(&mut self) -> &mut Vec<Node> {
+        &mut self.parameters
+    }
+
+    pub fn into_parts(self) -> (String, Vec<Node>) {
+        (self.name, self.parameters)
+    }
+}
\ No newline at end of file
@@ -13,8 +13,11 @@ use crate::nodes::global_scope::NodeGlobalScope;
 use crate::nodes::if_else::NodeIfElse;
 use crate::nodes::increment::NodeIncrement;
 use crate::nodes::literal::NodeLiteral;
+use crate::nodes::located::NodeLocated;
+use crate::nodes::named_func_call::NodeNamedFuncCall;
 use crate::nodes::not::NodeNot;
 use crate::nodes::ternary_op::NodeTernaryOp;
+use crate::nodes::try_catch::NodeTryCatch;
 use crate::nodes::var_const_call::NodeVarConstCall;
 use crate::nodes::var_decl::NodeVarDecl;
 
@@ -22,6 +25,7 @@ pub enum Node {
     Compound(NodeCompound),
     Literal(NodeLiteral),
     BuiltinFuncCall(NodeBuiltinFuncCall),
+    NamedFuncCall(NodeNamedFuncCall),
     GlobalScope(Box<NodeGlobalScope>),
     VarConstCall(NodeVarConstCall),
     VarDecl(Box<NodeVarDecl>),
@@ -37,8 +41,21 @@ pub enum Node {
     If(Box<NodeIf>),
     IfElse(Box<NodeIfElse>),
     While(Box<NodeWhile>),
+    TryCatch(Box<NodeTryCatch>),
 
     Break(NodeBreak),
     Continue(NodeContinue),
     BlankReturn(NodeBlankReturn),
+
+    Located(Box<NodeLocated>),
+}
+
+impl Node {
+    pub fn as_variable(&self) -> Option<&NodeVarConstCall> {
+        match self {
+            Node::VarConstCall(vc_call) => Some(vc_call),
+            Node::Located(located) => located.get_node().as_variable(),
+            _ => None,
+        }
+    }
 }
\ No newline at end of file
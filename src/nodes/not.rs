@@ -12,4 +12,12 @@ impl NodeNot {
     pub fn get_operand(&self) -> &Node {
         &self.operand
     }
+
+    pub fn get_operand_mut(&mut self) -> &mut Node {
+        &mut self.operand
+    }
+
+    pub fn into_operand(self) -> Node {
+        self.operand
+    }
 }
\ No newline at end of file
@@ -0,0 +1,16 @@
+﻿use std::fmt::{Display, Formatter};
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Slot {
+    Local(usize),
+    Global(usize),
+}
+
+impl Display for Slot {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Slot::Local(index) => write!(f, "local#{index}"),
+            Slot::Global(index) => write!(f, "global#{index}"),
+        }
+    }
+}
\ No newline at end of file
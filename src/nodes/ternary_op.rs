@@ -14,12 +14,28 @@ impl NodeTernaryOp {
     pub fn get_condition(&self) -> &Node {
         &self.condition
     }
+
+    pub fn get_condition_mut(&mut self) -> &mut Node {
+        &mut self.condition
+    }
     
     pub fn get_true_expr(&self) -> &Node {
         &self.true_expr
     }
+
+    pub fn get_true_expr_mut(&mut self) -> &mut Node {
+        &mut self.true_expr
+    }
     
     pub fn get_false_expr(&self) -> &Node {
         &self.false_expr
     }
+
+    pub fn get_false_expr_mut(&mut self) -> &mut Node {
+        &mut self.false_expr
+    }
+
+    pub fn into_parts(self) -> (Node, Node, Node) {
+        (self.condition, self.true_expr, self.false_expr)
+    }
 }
\ No newline at end of file
@@ -0,0 +1,60 @@
+﻿use crate::nodes::node::Node;
+
+pub struct NodeTryCatch {
+    try_body: Node,
+    error_name: String,
+    error_details: String,
+    catch_body: Node,
+    finally_body: Option<Node>,
+    binding_slots: (Option<usize>, Option<usize>),
+}
+
+impl NodeTryCatch {
+    pub fn init(try_body: Node, error_name: String, error_details: String, catch_body: Node, finally_body: Option<Node>) -> NodeTryCatch {
+        NodeTryCatch { try_body, error_name, error_details, catch_body, finally_body, binding_slots: (None, None) }
+    }
+
+    pub fn get_try_body(&self) -> &Node {
+        &self.try_body
+    }
+
+    pub fn get_try_body_mut(&mut self) -> &mut Node {
+        &mut self.try_body
+    }
+
+    pub fn get_error_name(&self) -> &String {
+        &self.error_name
+    }
+
+    pub fn get_error_details(&self) -> &String {
+        &self.error_details
+    }
+
+    pub fn get_catch_body(&self) -> &Node {
+        &self.catch_body
+    }
+
+    pub fn get_catch_body_mut(&mut self) -> &mut Node {
+        &mut self.catch_body
+    }
+
+    pub fn get_finally_body(&self) -> Option<&Node> {
+        self.finally_body.as_ref()
+    }
+
+    pub fn get_finally_body_mut(&mut self) -> Option<&mut Node> {
+        self.finally_body.as_mut()
+    }
+
+    pub fn get_binding_slots(&self) -> (Option<usize>, Option<usize>) {
+        self.binding_slots
+    }
+
+    pub fn set_binding_slots(&mut self, name_slot: Option<usize>, details_slot: Option<usize>) {
+        self.binding_slots = (name_slot, details_slot);
+    }
+
+    pub fn into_parts(self) -> (Node, String, String, Node, Option<Node>) {
+        (self.try_body, self.error_name, self.error_details, self.catch_body, self.finally_body)
+    }
+}
\ No newline at end of file
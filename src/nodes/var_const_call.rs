@@ -1,13 +1,24 @@
-﻿pub struct NodeVarConstCall {
+﻿use crate::nodes::slot::Slot;
+
+pub struct NodeVarConstCall {
     name: String,
+    slot: Option<Slot>,
 }
 
 impl NodeVarConstCall {
     pub fn init(name: String) -> NodeVarConstCall {
-        NodeVarConstCall { name }
+        NodeVarConstCall { name, slot: None }
     }
     
     pub fn get_name(&self) -> &String {
         &self.name
     }
+
+    pub fn get_slot(&self) -> Option<Slot> {
+        self.slot
+    }
+
+    pub fn set_slot(&mut self, slot: Slot) {
+        self.slot = Some(slot);
+    }
 }
\ No newline at end of file
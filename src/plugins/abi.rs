@@ -0,0 +1,51 @@
+﻿use std::ffi::c_char;
+
+// Dynamic plugins only exchange the `#[repr(C)]` types below with the host, since Rust types
+// such as trait objects have no stable layout across separately compiled libraries.
+pub const APICA_PLUGIN_ABI_VERSION: u32 = 1;
+
+pub const APICA_PLUGIN_NULL: u32 = 0;
+pub const APICA_PLUGIN_BOOL: u32 = 1;
+pub const APICA_PLUGIN_INTEGER: u32 = 2;
+pub const APICA_PLUGIN_FLOAT: u32 = 3;
+pub const APICA_PLUGIN_STRING: u32 = 4;
+pub const APICA_PLUGIN_ERROR: u32 = 5;
+
+pub const APICA_PLUGIN_VARIADIC: u32 = u32::MAX;
+
+#[repr(C)]
+pub struct ApicaPluginValue {
+    pub kind: u32,
+    pub integer: i64,
+    pub float: f64,
+    pub text: *const u8,
+    pub length: usize,
+}
+
+impl ApicaPluginValue {
+    pub fn null() -> ApicaPluginValue {
+        ApicaPluginValue { kind: APICA_PLUGIN_NULL, integer: 0, float: 0.0, text: std::ptr::null(), length: 0 }
+    }
+}
+
+pub type ApicaPluginCallback = unsafe extern "C" fn(arguments: *const ApicaPluginValue, count: usize, result: *mut ApicaPluginValue);
+pub type ApicaPluginRelease = unsafe extern "C" fn(value: *mut ApicaPluginValue);
+pub type ApicaPluginEntry = unsafe extern "C" fn() -> *const ApicaPluginTable;
+
+#[repr(C)]
+pub struct ApicaPluginBuiltin {
+    pub name: *const c_char,
+    pub min_arity: u32,
+    pub max_arity: u32,
+    pub required_right: u8,
+    pub callback: ApicaPluginCallback,
+}
+
+#[repr(C)]
+pub struct ApicaPluginTable {
+    pub abi_version: u32,
+    pub name: *const c_char,
+    pub builtins: *const ApicaPluginBuiltin,
+    pub builtin_count: usize,
+    pub release: Option<ApicaPluginRelease>,
+}
\ No newline at end of file
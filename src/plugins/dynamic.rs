@@ -0,0 +1,190 @@
+﻿use std::ffi::{c_char, CStr};
+use std::path::Path;
+use apica_common::element::{Element, ElementModifier};
+use apica_common::values::bool::ValueBool;
+use apica_common::values::error::ValueError;
+use apica_common::values::f64::ValueF64;
+use apica_common::values::i64::ValueI64;
+use apica_common::values::string::ValueString;
+use apica_common::values::value::Value;
+use libloading::{Library, Symbol};
+use crate::builtins::host::HostServices;
+use crate::builtins::registry::BuiltinHandler;
+use crate::plugins::abi::{ApicaPluginBuiltin, ApicaPluginCallback, ApicaPluginEntry, ApicaPluginRelease, ApicaPluginValue, APICA_PLUGIN_ABI_VERSION,
+                          APICA_PLUGIN_BOOL, APICA_PLUGIN_ERROR, APICA_PLUGIN_FLOAT, APICA_PLUGIN_INTEGER, APICA_PLUGIN_NULL, APICA_PLUGIN_STRING, APICA_PLUGIN_VARIADIC};
+use crate::systems::checker::TypeClass;
+use crate::utils::format;
+use crate::utils::math::Number;
+use crate::utils::rights::ApicaRight;
+
+pub const APICA_PLUGIN_DIRECTORY: &str = "plugins";
+
+pub type LoadedPlugin = (Library, String, Vec<Box<dyn BuiltinHandler>>);
+
+struct ForeignBuiltin {
+    name: String,
+    arity: (usize, Option<usize>),
+    required_right: Option<ApicaRight>,
+    callback: ApicaPluginCallback,
+    release: Option<ApicaPluginRelease>,
+}
+
+impl BuiltinHandler for ForeignBuiltin {
+    fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    fn get_arity(&self) -> (usize, Option<usize>) {
+        self.arity
+    }
+
+    fn get_parameter_types(&self) -> &[TypeClass] {
+        &[]
+    }
+
+    fn get_result_type(&self) -> TypeClass {
+        TypeClass::Unknown
+    }
+
+    fn get_required_right(&self) -> Option<ApicaRight> {
+        self.required_right
+    }
+
+    fn call(&self, parameters: Vec<Element>, _host: &mut HostServices) -> Element {
+        let texts: Vec<Option<String>> = parameters.iter().map(|parameter| match parameter.get_value() {
+            Value::String(_) => Some(format::element_to_string(parameter)),
+            _ => None,
+        }).collect();
+
+        let arguments: Vec<ApicaPluginValue> = parameters.iter().zip(&texts).map(|(parameter, text)| to_plugin_value(parameter, text.as_deref())).collect();
+        let mut result = ApicaPluginValue::null();
+
+        // SAFETY: the arguments and their strings outlive the call, and the plugin only reads them.
+        // The result stays valid until it is handed back to the plugin's release function.
+        unsafe {
+            (self.callback)(arguments.as_ptr(), arguments.len(), &mut result);
+            let element = from_plugin_value(&result, &self.name);
+            if let Some(release) = self.release {
+                release(&mut result);
+            }
+
+            element
+        }
+    }
+}
+
+fn to_plugin_value(parameter: &Element, text: Option<&str>) -> ApicaPluginValue {
+    let mut value = ApicaPluginValue::null();
+    if let Some(text) = text {
+        value.kind = APICA_PLUGIN_STRING;
+        value.text = text.as_ptr();
+        value.length = text.len();
+    } else if let Value::Bool(bool_value) = parameter.get_value() {
+        value.kind = APICA_PLUGIN_BOOL;
+        value.integer = bool_value.get_value().unwrap_or(false) as i64;
+    } else {
+        match Number::from_element(parameter) {
+            Some(Number::Integer(integer)) => {
+                value.kind = APICA_PLUGIN_INTEGER;
+                value.integer = integer;
+            },
+            Some(Number::Float(float)) => {
+                value.kind = APICA_PLUGIN_FLOAT;
+                value.float = float;
+            },
+            None => {},
+        }
+    }
+
+    value
+}
+
+unsafe fn read_text(value: &ApicaPluginValue) -> String {
+    if value.text.is_null() {
+        return String::new();
+    }
+
+    // SAFETY: the plugin guarantees that `text` points to `length` readable bytes.
+    String::from_utf8_lossy(unsafe { std::slice::from_raw_parts(value.text, value.length) }).to_string()
+}
+
+unsafe fn from_plugin_value(value: &ApicaPluginValue, name: &str) -> Element {
+    match value.kind {
+        APICA_PLUGIN_NULL => Element::create_null(),
+        APICA_PLUGIN_BOOL => Element::init(ElementModifier::None, Value::Bool(ValueBool::init_with(value.integer != 0))),
+        APICA_PLUGIN_INTEGER => Element::init(ElementModifier::None, Value::I64(ValueI64::init_with(value.integer))),
+        APICA_PLUGIN_FLOAT => Element::init(ElementModifier::None, Value::F64(ValueF64::init_with(value.float))),
+        APICA_PLUGIN_STRING => Element::init(ElementModifier::None, Value::String(ValueString::init_with(unsafe { read_text(value) }))),
+        APICA_PLUGIN_ERROR => plugin_error(unsafe { read_text(value) }),
+        kind => plugin_error(format!("The plugin builtin `{name}` returned an unknown value kind -> {kind}")),
+    }
+}
+
+unsafe fn read_name(name: *const c_char) -> Result<String, String> {
+    if name.is_null() {
+        return Err(String::from("The plugin exported a null name"));
+    }
+
+    // SAFETY: a non-null name is a NUL-terminated string owned by the plugin table.
+    match unsafe { CStr::from_ptr(name) }.to_str() {
+        Ok(name) => Ok(name.to_string()),
+        Err(_) => Err(String::from("The plugin exported a name that is not valid UTF-8")),
+    }
+}
+
+unsafe fn read_builtin(builtin: &ApicaPluginBuiltin, release: Option<ApicaPluginRelease>) -> Result<Box<dyn BuiltinHandler>, String> {
+    let name = unsafe { read_name(builtin.name) }?;
+    let required_right = match builtin.required_right {
+        0 => None,
+        bits => match ApicaRight::from_bits(bits) {
+            Some(right) => Some(right),
+            None => return Err(format!("The builtin `{name}` requires an unknown right -> {bits}")),
+        },
+    };
+
+    let max_arity = if builtin.max_arity == APICA_PLUGIN_VARIADIC { None } else { Some(builtin.max_arity as usize) };
+    Ok(Box::new(ForeignBuiltin { name, arity: (builtin.min_arity as usize, max_arity), required_right, callback: builtin.callback, release }))
+}
+
+pub fn load_plugin(path: &Path) -> Result<LoadedPlugin, String> {
+    // SAFETY: the library is trusted to export `apica_plugin_table` returning a table that lives
+    // as long as the library, with the layout declared in `plugins::abi`.
+    unsafe {
+        let library = Library::new(path).map_err(|error| error.to_string())?;
+
+        let (name, handlers) = {
+            let entry: Symbol<ApicaPluginEntry> = library.get(b"apica_plugin_table").map_err(|error| error.to_string())?;
+            let table = match entry().as_ref() {
+                Some(table) => table,
+                None => return Err(String::from("The plugin did not provide its builtin table")),
+            };
+
+            if table.abi_version != APICA_PLUGIN_ABI_VERSION {
+                return Err(format!("The plugin ABI version {} is not supported (expected {APICA_PLUGIN_ABI_VERSION})", table.abi_version));
+            }
+
+            let name = read_name(table.name)?;
+            let builtins = if table.builtin_count == 0 || table.builtins.is_null() {
+                &[]
+            } else {
+                std::slice::from_raw_parts(table.builtins, table.builtin_count)
+            };
+
+            let mut handlers = vec![];
+            for builtin in builtins {
+                handlers.push(read_builtin(builtin, table.release)?);
+            }
+
+            (name, handlers)
+        };
+
+        Ok((library, name, handlers))
+    }
+}
+
+fn plugin_error(details: String) -> Element {
+    Element::create_error(Value::Error(ValueError::init_with(
+        String::from("PluginError"),
+        Some(details),
+    )))
+}
\ No newline at end of file
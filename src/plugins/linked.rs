@@ -0,0 +1,8 @@
+﻿use crate::plugins::plugin::ApicaPlugin;
+use crate::plugins::strings::StringsPlugin;
+
+pub fn linked_plugins() -> Vec<Box<dyn ApicaPlugin>> {
+    vec![
+        Box::new(StringsPlugin),
+    ]
+}
\ No newline at end of file
@@ -0,0 +1,10 @@
+﻿#[cfg(any(feature = "linked-plugins", feature = "dynamic-plugins"))]
+pub mod plugin;
+#[cfg(feature = "linked-plugins")]
+pub mod linked;
+#[cfg(feature = "linked-plugins")]
+pub mod strings;
+#[cfg(feature = "dynamic-plugins")]
+pub mod abi;
+#[cfg(feature = "dynamic-plugins")]
+pub mod dynamic;
\ No newline at end of file
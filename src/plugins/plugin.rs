@@ -0,0 +1,37 @@
+﻿use crate::builtins::registry::{BuiltinHandler, BuiltinRegistry};
+
+#[cfg(feature = "linked-plugins")]
+pub trait ApicaPlugin {
+    fn get_name(&self) -> &str;
+
+    fn register(&self, registrar: &mut PluginRegistrar);
+}
+
+pub struct PluginRegistrar<'a> {
+    registry: &'a mut BuiltinRegistry,
+    registered: Vec<String>,
+    conflicts: Vec<String>,
+}
+
+impl<'a> PluginRegistrar<'a> {
+    pub fn init(registry: &'a mut BuiltinRegistry) -> PluginRegistrar<'a> {
+        PluginRegistrar { registry, registered: vec![], conflicts: vec![] }
+    }
+
+    pub fn add_builtin(&mut self, handler: Box<dyn BuiltinHandler>) {
+        let name = handler.get_name().to_string();
+        if self.registry.register_named(handler) {
+            self.registered.push(name);
+        } else {
+            self.conflicts.push(name);
+        }
+    }
+
+    pub fn get_registered(&self) -> &Vec<String> {
+        &self.registered
+    }
+
+    pub fn get_conflicts(&self) -> &Vec<String> {
+        &self.conflicts
+    }
+}
\ No newline at end of file
@@ -0,0 +1,49 @@
+﻿use apica_common::element::{Element, ElementModifier};
+use apica_common::values::bool::ValueBool;
+use apica_common::values::i64::ValueI64;
+use apica_common::values::string::ValueString;
+use apica_common::values::value::Value;
+use crate::builtins::host::HostServices;
+use crate::builtins::registry::BuiltinFunction;
+use crate::plugins::plugin::{ApicaPlugin, PluginRegistrar};
+use crate::systems::checker::TypeClass;
+use crate::utils::format;
+
+pub struct StringsPlugin;
+
+fn text_element(value: String) -> Element {
+    Element::init(ElementModifier::None, Value::String(ValueString::init_with(value)))
+}
+
+fn str_length(parameters: Vec<Element>, _host: &mut HostServices) -> Element {
+    let length = format::element_to_string(&parameters[0]).chars().count();
+    Element::init(ElementModifier::None, Value::I64(ValueI64::init_with(length as i64)))
+}
+
+fn str_upper(parameters: Vec<Element>, _host: &mut HostServices) -> Element {
+    text_element(format::element_to_string(&parameters[0]).to_uppercase())
+}
+
+fn str_lower(parameters: Vec<Element>, _host: &mut HostServices) -> Element {
+    text_element(format::element_to_string(&parameters[0]).to_lowercase())
+}
+
+fn str_contains(parameters: Vec<Element>, _host: &mut HostServices) -> Element {
+    let contains = format::element_to_string(&parameters[0]).contains(&format::element_to_string(&parameters[1]));
+    Element::init(ElementModifier::None, Value::Bool(ValueBool::init_with(contains)))
+}
+
+impl ApicaPlugin for StringsPlugin {
+    fn get_name(&self) -> &str {
+        "strings"
+    }
+
+    fn register(&self, registrar: &mut PluginRegistrar) {
+        const TEXT: TypeClass = TypeClass::String;
+
+        registrar.add_builtin(BuiltinFunction::init("StrLength", (1, Some(1)), &[TEXT], TypeClass::Numeric, None, str_length));
+        registrar.add_builtin(BuiltinFunction::init("StrUpper", (1, Some(1)), &[TEXT], TypeClass::String, None, str_upper));
+        registrar.add_builtin(BuiltinFunction::init("StrLower", (1, Some(1)), &[TEXT], TypeClass::String, None, str_lower));
+        registrar.add_builtin(BuiltinFunction::init("StrContains", (2, Some(2)), &[TEXT, TEXT], TypeClass::Bool, None, str_contains));
+    }
+}
\ No newline at end of file
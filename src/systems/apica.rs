@@ -1,35 +1,109 @@
-﻿use apica_common::bytecodes::ApicaEntrypointBytecode;
+﻿use std::collections::HashMap;
+use std::time::{Duration, Instant, SystemTime};
+use apica_common::bytecodes::ApicaEntrypointBytecode;
+use apica_common::element::{Element, ElementModifier};
+use apica_common::values::string::ValueString;
 use apica_common::values::value::Value;
 use winit::application::ApplicationHandler;
 use winit::event::WindowEvent;
 use winit::event_loop::ActiveEventLoop;
 use winit::window::{Window, WindowId};
-use crate::systems::evaluator::EvaluatorSystem;
+use crate::builtins::host::{AppServices, HostServices};
+use crate::systems::checker::TypeCheckerSystem;
+use crate::systems::clock::ClockSystem;
+use crate::systems::compiler::{CompilerSystem, Program};
+use crate::systems::evaluator::{EvaluatorSystem, DEFAULT_EVALUATION_DEPTH, DEFAULT_EXECUTION_BUDGET, DEFAULT_MEMORY_QUOTA};
 use crate::systems::inputs::InputsSystem;
 use crate::systems::logger::LoggerSystem;
-use crate::systems::reader::BytecodeReaderSystem;
+use crate::systems::mods::{LoadedMod, ModSystem};
+use crate::systems::optimizer::OptimizerSystem;
+use crate::systems::plugins::PluginSystem;
+use crate::systems::random::RandomSystem;
+use crate::systems::reader::{self, BytecodeReaderSystem, DEFAULT_READ_DEPTH};
+use crate::systems::resolver::ResolverSystem;
 use crate::systems::rights::RightSystem;
+use crate::systems::storage::DEFAULT_STORAGE_QUOTA;
 use crate::systems::window::WindowSystem;
-use crate::utils::rights::{ApicaMode, ApicaRight, APICA_MAIN_MENU};
+use crate::utils::config::{ExecutionEngine, LogFormat, LogLevel, SystemConfig, TimeoutPolicy, APICA_CONFIG_FILE, APICA_POLICY_FILE};
+use crate::utils::rights::{self, ApicaCapability, ApicaMode, ApicaRight, APICA_MAIN_MENU};
+
+const HOT_RELOAD_INTERVAL: Duration = Duration::from_millis(500);
 
 pub struct ApicaSystem {
     rights: RightSystem,
     reader: BytecodeReaderSystem,
     logger: LoggerSystem,
     evaluator: EvaluatorSystem,
+    resolver: ResolverSystem,
     window: WindowSystem,
     inputs: InputsSystem,
+    clock: ClockSystem,
+    random: RandomSystem,
+    services: AppServices,
+    policy: SystemConfig,
+    mods: ModSystem,
+    enable_mods: bool,
+    execution_budget: u64,
+    timeout_policy: TimeoutPolicy,
+    optimize: bool,
+    type_check: bool,
+    dump_optimized_tree: bool,
+    engine: ExecutionEngine,
+    programs: HashMap<u64, Program>,
+    app_name: String,
+    hot_reload: bool,
+    app_modified: Option<SystemTime>,
+    last_reload_check: Instant,
+    // Declared last so that plugin libraries outlive the builtins they registered.
+    _plugins: PluginSystem,
 }
 
 impl ApicaSystem {
     pub fn init() -> ApicaSystem {
+        let config = SystemConfig::load(APICA_CONFIG_FILE);
+
+        let mut logger = LoggerSystem::init(config.get_bool("logging").unwrap_or(true));
+        logger.set_min_level(config.get_string("log_level").and_then(|level| LogLevel::parse(level)).unwrap_or(LogLevel::Info));
+        logger.set_format(config.get_string("log_format").and_then(|format| LogFormat::parse(format)).unwrap_or(LogFormat::Plain));
+        logger.set_console(config.get_bool("log_console").unwrap_or(false));
+        logger.set_system_min_level(config.get_string("system_log_level").and_then(|level| LogLevel::parse(level)).unwrap_or(LogLevel::Info));
+
+        let mut reader = BytecodeReaderSystem::init();
+        reader.set_max_depth(get_depth_setting(&config, "max_read_depth", DEFAULT_READ_DEPTH, &mut logger));
+
+        let mut evaluator = EvaluatorSystem::init();
+        evaluator.set_max_depth(get_depth_setting(&config, "max_evaluation_depth", DEFAULT_EVALUATION_DEPTH, &mut logger));
+        evaluator.set_memory_quota(config.get_u64("memory_quota").unwrap_or(DEFAULT_MEMORY_QUOTA));
+
+        let mut plugins = PluginSystem::init();
+        plugins.load_plugins(evaluator.get_registry_mut(), &mut logger);
+
         ApicaSystem{
             rights: RightSystem::init(),
-            reader: BytecodeReaderSystem::init(),
-            logger: LoggerSystem::init(true),
-            evaluator: EvaluatorSystem::init(),
+            reader,
+            logger,
+            evaluator,
+            resolver: ResolverSystem::init(),
             window: WindowSystem::init(),
             inputs: InputsSystem::init(),
+            clock: ClockSystem::init(),
+            random: RandomSystem::init(config.get_u64("random_seed")),
+            services: AppServices::init(config.get_u64("storage_quota").unwrap_or(DEFAULT_STORAGE_QUOTA)),
+            policy: SystemConfig::load(APICA_POLICY_FILE),
+            mods: ModSystem::init(),
+            enable_mods: config.get_bool("mods").unwrap_or(true),
+            execution_budget: config.get_u64("execution_budget").unwrap_or(DEFAULT_EXECUTION_BUDGET),
+            timeout_policy: config.get_string("timeout_policy").and_then(|policy| TimeoutPolicy::parse(policy)).unwrap_or(TimeoutPolicy::QuitApp),
+            optimize: config.get_bool("optimize").unwrap_or(true),
+            type_check: config.get_bool("type_check").unwrap_or(true),
+            dump_optimized_tree: config.get_bool("dump_optimized_tree").unwrap_or(false),
+            engine: config.get_string("engine").and_then(|engine| ExecutionEngine::parse(engine)).unwrap_or(ExecutionEngine::VirtualMachine),
+            programs: HashMap::new(),
+            app_name: String::new(),
+            hot_reload: config.get_bool("hot_reload").unwrap_or(true),
+            app_modified: None,
+            last_reload_check: Instant::now(),
+            _plugins: plugins,
         }
     }
 
@@ -46,16 +120,341 @@ impl ApicaSystem {
             return;
         }
 
-        self.evaluator.clear_data();
-        self.logger.create_file_for(app_name);
-        self.reader.read_app(app_name, &mut self.logger);
+        self.evaluator.clear_data(&mut self.random);
+        self.logger.set_app(app_name);
+        self.app_name = app_name.to_string();
+        self.app_modified = Self::get_modified_time(app_name);
+        let loaded = self.build_app(app_name, &reader::get_app_filepath(app_name));
+        if loaded {
+            self.evaluator.prepare_globals(self.resolver.get_globals().len());
+        }
+
+        let app_id = self.get_app_id();
+        self.logger.set_app_id(app_id.as_deref());
+        self.apply_capabilities(app_id.as_deref().unwrap_or(app_name));
+        self.services.get_state().set_app(app_id.as_deref().unwrap_or(app_name), self.resolver.get_globals());
+        self.services.get_files().set_app(app_id.as_deref().unwrap_or(app_name));
+        if !self.services.get_storage().set_app(app_id.as_deref()) {
+            self.logger.system_logn_error(format!("The storage of `{app_name}` is corrupted and was ignored"));
+        }
+
+        self.mods.clear();
+        if loaded && self.enable_mods {
+            self.load_mods(app_id.as_deref().unwrap_or(app_name));
+        }
+
+        self.apply_specifications();
+    }
+
+    fn build_app(&mut self, app_name: &str, filepath: &str) -> bool {
+        let mut loaded = self.reader.read_file(filepath, app_name, &mut self.logger);
+        if loaded && self.optimize {
+            self.optimize_app(app_name);
+        }
+
+        if loaded {
+            loaded = self.resolve_app(app_name);
+        }
+
+        if loaded && self.type_check {
+            loaded = self.check_app(app_name);
+        }
+
+        self.programs.clear();
+        if loaded && self.engine == ExecutionEngine::VirtualMachine {
+            self.compile_app();
+        }
+
+        loaded
+    }
+
+    fn load_mods(&mut self, app_id: &str) {
+        if !self.rights.has_right(ApicaRight::ModRight) {
+            return;
+        }
+
+        for (mod_name, filepath) in ModSystem::find_mods(app_id, &mut self.logger) {
+            let mut mod_reader = BytecodeReaderSystem::init();
+            mod_reader.set_max_depth(self.reader.get_max_depth());
+            let mut mod_resolver = ResolverSystem::init();
+            let mut mod_programs = HashMap::new();
+
+            self.swap_code(&mut mod_reader, &mut mod_resolver, &mut mod_programs);
+            let loaded = self.build_app(&mod_name, &filepath);
+            self.swap_code(&mut mod_reader, &mut mod_resolver, &mut mod_programs);
+
+            if loaded {
+                let global_count = mod_resolver.get_globals().len();
+                let capabilities = get_requested_capabilities(&mod_reader, ApicaCapability::empty()) & self.rights.get_capabilities();
+                self.mods.add(LoadedMod::init(&mod_name, mod_reader, mod_programs, global_count, capabilities), &mut self.logger);
+            }
+        }
+
+        if self.mods.get_count() > 0 {
+            self.logger.system_logn_success(format!("{} mod(s) were loaded for `{app_id}`", self.mods.get_count()));
+        }
+    }
+
+    fn swap_code(&mut self, reader: &mut BytecodeReaderSystem, resolver: &mut ResolverSystem, programs: &mut HashMap<u64, Program>) {
+        std::mem::swap(&mut self.reader, reader);
+        std::mem::swap(&mut self.resolver, resolver);
+        std::mem::swap(&mut self.programs, programs);
+    }
+
+    fn swap_mod(&mut self, index: usize) {
+        let loaded_mod = &mut self.mods.get_mods_mut()[index];
+        loaded_mod.swap_code(&mut self.reader, &mut self.programs);
+        self.evaluator.swap_globals(loaded_mod.get_globals_mut());
+    }
+
+    fn run_mod_hooks(&mut self, entry: ApicaEntrypointBytecode) {
+        if self.mods.get_count() == 0 {
+            return;
+        }
+
+        let right = self.rights.get_right();
+        let capabilities = self.rights.get_capabilities();
+        self.rights.set_right(ApicaRight::Mod);
+        for index in 0..self.mods.get_count() {
+            self.rights.set_capabilities(self.mods.get_mods_mut()[index].get_capabilities());
+            self.swap_mod(index);
+            self.run_entry(entry);
+            self.swap_mod(index);
+        }
+
+        self.rights.set_right(right);
+        self.rights.set_capabilities(capabilities);
+    }
+
+    fn apply_capabilities(&mut self, app_id: &str) {
+        let requested = if self.app_name == APICA_MAIN_MENU {
+            ApicaCapability::all()
+        } else {
+            get_requested_capabilities(&self.reader, ApicaCapability::Default)
+        };
+
+        self.rights.set_capabilities(rights::apply_policy(&self.policy, app_id, requested));
+    }
+
+    fn get_app_id(&self) -> Option<String> {
+        if let Some(Value::String(id)) = self.reader.get_data("id") && let Some(id) = id.get_value()
+            && !id.is_empty() && !id.starts_with('.')
+            && id.chars().all(|character| character.is_ascii_alphanumeric() || matches!(character, '.' | '_' | '-')) {
+            return Some(id.clone());
+        }
+
+        None
+    }
 
+    fn apply_specifications(&mut self) {
         let title = if let Some(value) = self.reader.get_data("title") && let Value::String(title) = value {
             title.get_value().as_ref().unwrap()
         } else {
             "???"
         };
         self.window.system_set_title(title);
+
+        let activate = if let Some(value) = self.reader.get_data("logger") && let Value::Bool(activate) = value {
+            activate.get_value().unwrap_or(true)
+        } else {
+            true
+        };
+        self.logger.set_app_activation(activate);
+
+        let budget = if let Some(value) = self.reader.get_data("execution_budget") && let Value::U32(budget) = value {
+            budget.get_value().map(|budget| budget as u64).unwrap_or(self.execution_budget)
+        } else {
+            self.execution_budget
+        };
+        self.evaluator.set_budget(budget);
+    }
+
+    fn optimize_app(&mut self, app_name: &str) {
+        if self.dump_optimized_tree {
+            self.logger.system_write_file(&format!("{app_name}.original.tree"), &self.reader.dump_entries());
+        }
+
+        let mut optimizer = OptimizerSystem::init();
+        self.reader.optimize(&mut optimizer);
+        self.logger.system_logn_success(format!("App was optimized: {}", optimizer.get_summary()));
+
+        if self.dump_optimized_tree {
+            self.logger.system_write_file(&format!("{app_name}.optimized.tree"), &self.reader.dump_entries());
+        }
+    }
+
+    fn resolve_app(&mut self, app_name: &str) -> bool {
+        self.resolver.clear();
+        for entry in reader::APICA_ENTRYPOINTS {
+            if let Some(root) = self.reader.get_entry_node_mut(entry) {
+                self.resolver.resolve_entry(root);
+            }
+        }
+
+        if !self.resolver.get_errors().is_empty() {
+            for error in self.resolver.get_errors() {
+                self.logger.system_logn_error(error.clone());
+            }
+
+            self.reader.clear();
+            self.logger.system_logn_error(format!("The APB file of `{app_name}` was rejected"));
+            return false;
+        }
+
+        true
+    }
+
+    fn get_modified_time(app_name: &str) -> Option<SystemTime> {
+        std::fs::metadata(reader::get_app_filepath(app_name)).and_then(|metadata| metadata.modified()).ok()
+    }
+
+    fn check_hot_reload(&mut self) {
+        if !self.hot_reload || self.last_reload_check.elapsed() < HOT_RELOAD_INTERVAL {
+            return;
+        }
+
+        self.last_reload_check = Instant::now();
+        let modified = Self::get_modified_time(&self.app_name);
+        if modified.is_none() || modified == self.app_modified {
+            return;
+        }
+
+        self.app_modified = modified;
+        self.reload_app();
+    }
+
+    fn reload_app(&mut self) {
+        let app_name = self.app_name.clone();
+        let mut new_reader = BytecodeReaderSystem::init();
+        new_reader.set_max_depth(self.reader.get_max_depth());
+
+        let previous_reader = std::mem::replace(&mut self.reader, new_reader);
+        let previous_resolver = std::mem::replace(&mut self.resolver, ResolverSystem::init());
+        let previous_programs = std::mem::take(&mut self.programs);
+
+        if !self.build_app(&app_name, &reader::get_app_filepath(&app_name)) {
+            self.reader = previous_reader;
+            self.resolver = previous_resolver;
+            self.programs = previous_programs;
+            self.logger.system_logn_error(format!("Hot reload of `{app_name}` failed, the previous code keeps running"));
+            return;
+        }
+
+        let mapping = self.resolver.match_globals(&previous_resolver);
+        let kept = self.evaluator.remap_globals(&mapping);
+        let app_id = self.get_app_id();
+        self.logger.set_app_id(app_id.as_deref());
+        self.apply_capabilities(app_id.as_deref().unwrap_or(&app_name));
+        self.services.get_state().set_app(app_id.as_deref().unwrap_or(&app_name), self.resolver.get_globals());
+        self.services.get_files().set_app(app_id.as_deref().unwrap_or(&app_name));
+        if !self.services.get_storage().set_app(app_id.as_deref()) {
+            self.logger.system_logn_error(format!("The storage of `{app_name}` is corrupted and was ignored"));
+        }
+
+        self.mods.clear();
+        if self.enable_mods {
+            self.load_mods(app_id.as_deref().unwrap_or(&app_name));
+        }
+
+        self.apply_specifications();
+        self.logger.system_logn_success(format!("App `{app_name}` was hot reloaded ({kept}/{} globals kept)", mapping.len()));
+    }
+
+    fn check_app(&mut self, app_name: &str) -> bool {
+        let mut checker = TypeCheckerSystem::init(self.resolver.get_globals(), self.evaluator.get_registry());
+        for entry in reader::APICA_ENTRYPOINTS {
+            if let Some(root) = self.reader.get_entry_node(entry) {
+                checker.check_entry(root);
+            }
+        }
+
+        if !checker.get_errors().is_empty() {
+            for error in checker.get_errors() {
+                self.logger.system_logn_error(error.clone());
+            }
+
+            self.reader.clear();
+            self.logger.system_logn_error(format!("The APB file of `{app_name}` was rejected"));
+            return false;
+        }
+
+        true
+    }
+
+    fn compile_app(&mut self) {
+        let mut compiler = CompilerSystem::init();
+        for entry in reader::APICA_ENTRYPOINTS {
+            if let Some(root) = self.reader.get_entry_node(entry) {
+                self.programs.insert(entry as u64, compiler.compile_entry(root));
+            }
+        }
+    }
+
+    fn run_entry(&mut self, entry: ApicaEntrypointBytecode) -> bool {
+        let mut host = HostServices::init(&mut self.logger, &mut self.rights, &mut self.window, &mut self.inputs, &self.clock, &mut self.random, &mut self.services);
+        if let Some(program) = self.programs.get(&(entry as u64)) {
+            self.evaluator.execute(entry, program, &mut host);
+            true
+        } else if let Some(root) = self.reader.get_entry_node(entry) {
+            self.evaluator.evaluate(entry, root, &mut host);
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn benchmark_engines(app_name: &str, iterations: u32) {
+        let mut apica_system = ApicaSystem::init();
+        apica_system.load_app(app_name);
+        apica_system.programs.clear();
+        apica_system.compile_app();
+
+        for engine in [ExecutionEngine::TreeWalker, ExecutionEngine::VirtualMachine] {
+            let programs = if engine == ExecutionEngine::TreeWalker { std::mem::take(&mut apica_system.programs) } else { HashMap::new() };
+
+            apica_system.evaluator.clear_data(&mut apica_system.random);
+            apica_system.clock.start_app();
+            apica_system.run_entry(ApicaEntrypointBytecode::Init);
+
+            let start = Instant::now();
+            for _ in 0..iterations {
+                apica_system.clock.begin_frame();
+                apica_system.run_entry(ApicaEntrypointBytecode::Update);
+            }
+            let elapsed = start.elapsed();
+
+            println!("{:?}: {} updates in {:.3} ms ({:.3} us/update)", engine, iterations, elapsed.as_secs_f64() * 1000.0,
+                     elapsed.as_secs_f64() * 1_000_000.0 / iterations.max(1) as f64);
+
+            if engine == ExecutionEngine::TreeWalker {
+                apica_system.programs = programs;
+            }
+        }
+    }
+
+    fn check_timeout(&mut self) {
+        if !self.evaluator.has_timed_out() {
+            return;
+        }
+
+        match self.timeout_policy {
+            TimeoutPolicy::SkipFrame => {
+                self.logger.system_logn_error("The entrypoint was aborted, resuming at the next frame".to_string());
+            },
+
+            TimeoutPolicy::QuitApp => {
+                if *self.rights.get_mode() != ApicaMode::Quit {
+                    self.logger.system_logn_error("The entrypoint was aborted, quitting the app".to_string());
+                    self.rights.set_mode(ApicaMode::Quit);
+                }
+            },
+        }
+    }
+
+    fn check_launch(&mut self) {
+        if self.services.get_launcher().has_request() && *self.rights.get_mode() != ApicaMode::Quit {
+            self.rights.set_mode(ApicaMode::Quit);
+        }
     }
 
     pub fn update_system(&mut self) {
@@ -68,18 +467,28 @@ impl ApicaSystem {
             },
 
             ApicaMode::Init => {
-                if let Some(init_node) = self.reader.get_entry_node(ApicaEntrypointBytecode::Init) {
-                    self.evaluator.evaluate(init_node, &mut self.logger, &mut self.rights, &mut self.window, &mut self.inputs);
-                } else {
+                self.clock.start_app();
+                self.logger.set_frame(self.clock.get_frame_count());
+                self.run_mod_hooks(ApicaEntrypointBytecode::BeforeInit);
+                if !self.run_entry(ApicaEntrypointBytecode::Init) {
                     self.logger.system_logn_error("Failed to load the init entrypoint of the app".to_string());
                 }
 
                 self.rights.set_mode(ApicaMode::Update);
+                self.check_timeout();
+                self.run_mod_hooks(ApicaEntrypointBytecode::Init);
+                self.check_launch();
             },
 
             ApicaMode::Update => {
-                if let Some(update_node) = self.reader.get_entry_node(ApicaEntrypointBytecode::Update) {
-                    self.evaluator.evaluate(update_node, &mut self.logger, &mut self.rights, &mut self.window, &mut self.inputs);
+                self.check_hot_reload();
+                self.clock.begin_frame();
+                self.logger.set_frame(self.clock.get_frame_count());
+                self.run_mod_hooks(ApicaEntrypointBytecode::BeforeUpdate);
+                if self.run_entry(ApicaEntrypointBytecode::Update) {
+                    self.check_timeout();
+                    self.run_mod_hooks(ApicaEntrypointBytecode::Update);
+                    self.check_launch();
                 } else {
                     self.rights.set_mode(ApicaMode::Quit);
                     self.logger.system_logn_error("Failed to load the update entrypoint of the app".to_string());
@@ -87,15 +496,23 @@ impl ApicaSystem {
             },
 
             ApicaMode::Quit => {
-                if let Some(quit_node) = self.reader.get_entry_node(ApicaEntrypointBytecode::Quit) {
-                    self.evaluator.evaluate(quit_node, &mut self.logger, &mut self.rights, &mut self.window, &mut self.inputs);
-                } else {
+                self.run_mod_hooks(ApicaEntrypointBytecode::BeforeQuit);
+                if !self.run_entry(ApicaEntrypointBytecode::Quit) {
                     self.logger.system_logn_error("Failed to load the quit entrypoint of the app".to_string());
                 }
 
-                if self.rights.has_right(ApicaRight::MainMenuRight) {
+                self.run_mod_hooks(ApicaEntrypointBytecode::Quit);
+                let peak_memory = format!("Peak memory usage of `{}`: {} bytes", self.app_name, self.evaluator.get_peak_memory());
+                self.logger.logn_success(vec![Element::init(ElementModifier::None, Value::String(ValueString::init_with(peak_memory)))]);
+
+                if let Some(request) = self.services.get_launcher().start_app() {
+                    self.rights.set_mode(ApicaMode::Init);
+                    self.rights.set_right(ApicaRight::App);
+                    self.load_app(request.get_app_name());
+                } else if self.rights.has_right(ApicaRight::MainMenuRight) {
                     self.rights.set_mode(ApicaMode::SpecialQuit);
                 } else if self.rights.has_right(ApicaRight::AppRight) {
+                    self.services.get_launcher().finish_app();
                     self.rights.set_mode(ApicaMode::Init);
                     self.rights.set_right(ApicaRight::MainMenu);
                     self.load_app(APICA_MAIN_MENU);
@@ -105,6 +522,25 @@ impl ApicaSystem {
     }
 }
 
+fn get_depth_setting(config: &SystemConfig, key: &str, default: u32, logger: &mut LoggerSystem) -> u32 {
+    match config.get_u64(key).map(u32::try_from) {
+        Some(Ok(depth)) => depth,
+        Some(Err(_)) => {
+            logger.system_logn_warning(format!("The setting `{key}` does not fit in a U32, the default of {default} is used instead"));
+            default
+        },
+        None => default,
+    }
+}
+
+fn get_requested_capabilities(reader: &BytecodeReaderSystem, default: ApicaCapability) -> ApicaCapability {
+    if let Some(Value::U32(capabilities)) = reader.get_data("capabilities") && let Some(capabilities) = capabilities.get_value() {
+        ApicaCapability::from_bits_truncate(capabilities)
+    } else {
+        default
+    }
+}
+
 impl ApplicationHandler for ApicaSystem {
     fn resumed(&mut self, event_loop: &ActiveEventLoop) {
         let attributes = Window::default_attributes()
@@ -0,0 +1,125 @@
+﻿use std::fs::{metadata, read_dir};
+use std::time::SystemTime;
+use apica_common::element::{Element, ElementModifier};
+use apica_common::values::error::ValueError;
+use apica_common::values::string::ValueString;
+use apica_common::values::u32::ValueU32;
+use apica_common::values::value::Value;
+use crate::systems::logger::LoggerSystem;
+use crate::systems::reader::{self, BytecodeReaderSystem};
+use crate::utils::rights::APICA_MAIN_MENU;
+
+pub const APICA_APPS_DIRECTORY: &str = "apps";
+pub const APP_INFO_FIELDS: [&str; 5] = ["title", "id", "version", "window_width", "window_height"];
+
+pub struct AppInfo {
+    name: String,
+    fields: Vec<(&'static str, Value)>,
+}
+
+impl AppInfo {
+    pub fn get_name(&self) -> &String {
+        &self.name
+    }
+
+    pub fn get_field(&self, field: &str) -> Option<&Value> {
+        self.fields.iter().find(|(name, _)| *name == field).map(|(_, value)| value)
+    }
+}
+
+pub struct CatalogSystem {
+    apps: Vec<AppInfo>,
+    signature: Option<Vec<(String, Option<SystemTime>)>>,
+}
+
+impl CatalogSystem {
+    pub fn init() -> CatalogSystem {
+        CatalogSystem { apps: vec![], signature: None }
+    }
+
+    pub fn get_apps(&mut self, logger: &mut LoggerSystem) -> &Vec<AppInfo> {
+        let signature = Self::scan_signature();
+        if self.signature.as_ref() != Some(&signature) {
+            self.apps = signature.iter().filter_map(|(name, _)| Self::read_app_info(name, logger)).collect();
+            self.signature = Some(signature);
+        }
+
+        &self.apps
+    }
+
+    pub fn list_apps(&mut self, logger: &mut LoggerSystem) -> Element {
+        let names = self.get_apps(logger).iter().map(|app| app.get_name().clone()).collect::<Vec<String>>();
+        Element::init(ElementModifier::None, Value::String(ValueString::init_with(names.join("\n"))))
+    }
+
+    pub fn get_app_count(&mut self, logger: &mut LoggerSystem) -> Element {
+        let count = self.get_apps(logger).len() as u32;
+        Element::init(ElementModifier::None, Value::U32(ValueU32::init_with(count)))
+    }
+
+    pub fn get_app_info(&mut self, app: &Value, field: &str, logger: &mut LoggerSystem) -> Element {
+        if field != "name" && !APP_INFO_FIELDS.contains(&field) {
+            return catalog_error(format!("Unknown app info field -> {field}"));
+        }
+
+        let apps = self.get_apps(logger);
+        let info = match app {
+            Value::String(name) => name.get_value().as_ref().and_then(|name| apps.iter().find(|app| app.get_name() == name)),
+            Value::U32(index) => index.get_value().and_then(|index| apps.get(index as usize)),
+            _ => None,
+        };
+
+        let info = match info {
+            Some(info) => info,
+            None => return catalog_error(String::from("Cannot find the requested app in the catalog")),
+        };
+
+        if field == "name" {
+            return Element::init(ElementModifier::None, Value::String(ValueString::init_with(info.get_name().clone())));
+        }
+
+        match info.get_field(field) {
+            Some(value) => Element::init(ElementModifier::None, value.clone()),
+            None => Element::create_null(),
+        }
+    }
+
+    fn scan_signature() -> Vec<(String, Option<SystemTime>)> {
+        let mut signature = vec![];
+        if let Ok(entries) = read_dir(APICA_APPS_DIRECTORY) {
+            for entry in entries.flatten() {
+                let name = entry.file_name().to_string_lossy().to_string();
+                if name == APICA_MAIN_MENU {
+                    continue;
+                }
+
+                if let Ok(file) = metadata(reader::get_app_filepath(&name)) && file.is_file() {
+                    signature.push((name, file.modified().ok()));
+                }
+            }
+        }
+
+        signature.sort();
+        signature
+    }
+
+    fn read_app_info(name: &str, logger: &mut LoggerSystem) -> Option<AppInfo> {
+        let mut reader = BytecodeReaderSystem::init();
+        if !reader.read_app_specifications(name, logger) {
+            return None;
+        }
+
+        let fields = APP_INFO_FIELDS.iter()
+            .filter_map(|field| reader.get_data(field).map(|value| (*field, value.clone())))
+            .collect();
+
+        Some(AppInfo { name: name.to_string(), fields })
+    }
+}
+
+fn catalog_error(details: String) -> Element {
+    Element::create_error(Value::Error(ValueError::init_with(
+        String::from("CatalogError"),
+        Some(details),
+    )))
+}
\ No newline at end of file
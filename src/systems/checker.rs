@@ -0,0 +1,345 @@
+﻿use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use apica_common::bytecodes::ApicaTypeBytecode;
+use apica_common::element::{Element, ElementModifier};
+use apica_common::values::value::Value;
+use crate::builtins::registry::{self, BuiltinHandler, BuiltinRegistry};
+use crate::nodes::builtin_func_call::NodeBuiltinFuncCall;
+use crate::nodes::compound::NodeCompound;
+use crate::nodes::located::SourceLocation;
+use crate::nodes::named_func_call::NodeNamedFuncCall;
+use crate::nodes::node::Node;
+use crate::nodes::slot::Slot;
+use crate::systems::resolver::GlobalDeclaration;
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum TypeClass {
+    Null,
+    Bool,
+    Numeric,
+    String,
+    Unknown,
+}
+
+impl TypeClass {
+    pub fn of_kind(kind: &ApicaTypeBytecode) -> TypeClass {
+        match kind {
+            ApicaTypeBytecode::Null => TypeClass::Null,
+            ApicaTypeBytecode::Bool => TypeClass::Bool,
+            ApicaTypeBytecode::String => TypeClass::String,
+            _ => TypeClass::Numeric,
+        }
+    }
+
+    pub fn of_value(value: &Value) -> TypeClass {
+        match value {
+            Value::Null(_) => TypeClass::Null,
+            Value::Bool(_) => TypeClass::Bool,
+            Value::String(_) => TypeClass::String,
+            Value::U8(_) | Value::U32(_) | Value::U64(_) | Value::I64(_) | Value::F64(_) => TypeClass::Numeric,
+            _ => TypeClass::Unknown,
+        }
+    }
+
+    pub fn accepts(&self, other: TypeClass) -> bool {
+        *self == TypeClass::Unknown || other == TypeClass::Unknown || *self == other
+    }
+
+    pub fn accepts_implicit(&self, other: TypeClass) -> bool {
+        self.accepts(other) || (*self == TypeClass::Bool && other == TypeClass::Numeric)
+    }
+}
+
+impl Display for TypeClass {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TypeClass::Null => write!(f, "null"),
+            TypeClass::Bool => write!(f, "bool"),
+            TypeClass::Numeric => write!(f, "number"),
+            TypeClass::String => write!(f, "string"),
+            TypeClass::Unknown => write!(f, "unknown"),
+        }
+    }
+}
+
+pub struct TypeCheckerSystem<'a> {
+    registry: &'a BuiltinRegistry,
+    globals: Vec<(TypeClass, bool)>,
+    locals: HashMap<usize, (TypeClass, bool)>,
+    locations: Vec<SourceLocation>,
+    errors: Vec<String>,
+}
+
+impl<'a> TypeCheckerSystem<'a> {
+    pub fn init(globals: &[GlobalDeclaration], registry: &'a BuiltinRegistry) -> TypeCheckerSystem<'a> {
+        TypeCheckerSystem {
+            registry,
+            globals: globals.iter().map(|global| (TypeClass::of_kind(global.get_value_kind()), global.is_const())).collect(),
+            locals: HashMap::new(),
+            locations: vec![],
+            errors: vec![],
+        }
+    }
+
+    pub fn get_errors(&self) -> &Vec<String> {
+        &self.errors
+    }
+
+    pub fn check_entry(&mut self, root: &NodeCompound) {
+        self.locals.clear();
+        self.locations.clear();
+        self.check_compound(root);
+    }
+
+    fn report(&mut self, name: &str, details: String) {
+        match self.locations.last() {
+            Some(location) => self.errors.push(format!("{name}: {details} (at {location})")),
+            None => self.errors.push(format!("{name}: {details}")),
+        }
+    }
+
+    fn slot_type(&self, slot: Option<Slot>) -> (TypeClass, bool) {
+        match slot {
+            Some(Slot::Local(index)) => self.locals.get(&index).copied(),
+            Some(Slot::Global(index)) => self.globals.get(index).copied(),
+            None => None,
+        }.unwrap_or((TypeClass::Unknown, false))
+    }
+
+    fn check_compound(&mut self, compound: &NodeCompound) -> TypeClass {
+        for node in compound.get_nodes() {
+            self.check_node(node);
+        }
+
+        TypeClass::Null
+    }
+
+    fn check_condition(&mut self, condition: &Node, statement: &str) {
+        let class = self.check_node(condition);
+        if !TypeClass::Bool.accepts_implicit(class) {
+            self.report("TypeError", format!("The condition of a `{statement}` must be a bool, found a {class}"));
+        }
+    }
+
+    fn check_declaration(&mut self, name: &String, kind: &ApicaTypeBytecode, is_const: bool, expression: &Node, slot: Option<Slot>) -> TypeClass {
+        let class = self.check_node(expression);
+        let expected = TypeClass::of_kind(kind);
+
+        let mut literal = expression;
+        while let Node::Located(located) = literal {
+            literal = located.get_node();
+        }
+
+        if let Node::Literal(literal) = literal {
+            let converted = Element::init(ElementModifier::None, literal.get_value().clone()).check_convert(*kind);
+            if converted.get_modifier().contains(ElementModifier::Error) {
+                self.report("TypeError", format!("The literal assigned to `{name}` cannot be converted to {:?}", kind));
+            }
+        } else if !expected.accepts_implicit(class) {
+            self.report("TypeError", format!("Cannot assign a {class} to `{name}` declared as {:?}", kind));
+        }
+
+        if let Some(Slot::Local(index)) = slot {
+            self.locals.insert(index, (expected, is_const));
+        }
+
+        TypeClass::Null
+    }
+
+    fn check_unary(&mut self, operand: &Node, symbol: &str, expected: TypeClass) -> TypeClass {
+        let class = self.check_node(operand);
+        if let Some(vc_call) = operand.as_variable() && self.slot_type(vc_call.get_slot()).1 {
+            let name = vc_call.get_name().clone();
+            self.report("ConstError", format!("Cannot perform a `right {symbol}` unary operation to the constant `{name}`"));
+        }
+
+        if !expected.accepts(class) {
+            self.report("TypeError", format!("Cannot perform a `right {symbol}` unary operation to a {class}"));
+        }
+
+        expected
+    }
+
+    fn check_builtin(&mut self, builtin: &NodeBuiltinFuncCall) -> TypeClass {
+        let classes: Vec<TypeClass> = builtin.get_parameters().iter().map(|parameter| self.check_node(parameter)).collect();
+        let function = builtin.get_function_bytecode();
+        match self.registry.get(function) {
+            Some(handler) => self.check_call(handler, classes),
+            None => {
+                self.report("AccessError", format!("An undefined builtin func-call was found -> {:?}", function));
+                TypeClass::Unknown
+            },
+        }
+    }
+
+    fn check_named(&mut self, named: &NodeNamedFuncCall) -> TypeClass {
+        let classes: Vec<TypeClass> = named.get_parameters().iter().map(|parameter| self.check_node(parameter)).collect();
+        match self.registry.get_named(named.get_name()) {
+            Some(handler) => self.check_call(handler, classes),
+            None => {
+                let name = named.get_name().clone();
+                self.report("AccessError", format!("An undefined named func-call was found -> {name}"));
+                TypeClass::Unknown
+            },
+        }
+    }
+
+    fn check_call(&mut self, handler: &dyn BuiltinHandler, classes: Vec<TypeClass>) -> TypeClass {
+        let name = handler.get_name().to_string();
+        let count = classes.len();
+        if !registry::accepts_arity(handler, count) {
+            let expected = match handler.get_arity() {
+                (min_arity, Some(max_arity)) if max_arity == min_arity => format!("{max_arity}"),
+                (min_arity, Some(max_arity)) => format!("{min_arity} to {max_arity}"),
+                (min_arity, None) => format!("at least {min_arity}"),
+            };
+            self.report("ArgumentError", format!("The function `{name}` expects {expected} argument(s) but receives {count}"));
+        }
+
+        for (index, (expected, class)) in handler.get_parameter_types().iter().zip(classes).enumerate() {
+            if !expected.accepts(class) {
+                self.report("ArgumentError", format!("The argument #{} of `{name}` must be a {expected}, found a {class}", index + 1));
+            }
+        }
+
+        handler.get_result_type()
+    }
+
+    fn check_node(&mut self, node: &Node) -> TypeClass {
+        match node {
+            Node::Compound(compound) => self.check_compound(compound),
+            Node::BuiltinFuncCall(builtin) => self.check_builtin(builtin),
+            Node::NamedFuncCall(named) => self.check_named(named),
+            Node::Literal(literal) => TypeClass::of_value(literal.get_value()),
+            Node::GlobalScope(global_scope) => self.check_node(global_scope.get_statement()),
+            Node::VarConstCall(vc_call) => self.slot_type(vc_call.get_slot()).0,
+
+            Node::VarDecl(var_decl) => {
+                self.check_declaration(var_decl.get_name(), var_decl.get_value_kind(), false, var_decl.get_expression(), var_decl.get_slot())
+            },
+
+            Node::ConstDecl(const_decl) => {
+                self.check_declaration(const_decl.get_name(), const_decl.get_value_kind(), true, const_decl.get_expression(), const_decl.get_slot())
+            },
+
+            Node::Add(add) => {
+                let left = self.check_node(add.get_left());
+                let right = self.check_node(add.get_right());
+                match (left, right) {
+                    (TypeClass::Unknown, _) | (_, TypeClass::Unknown) => TypeClass::Unknown,
+                    (TypeClass::Numeric, TypeClass::Numeric) => TypeClass::Numeric,
+                    (TypeClass::String, TypeClass::String | TypeClass::Numeric) | (TypeClass::Numeric, TypeClass::String) => TypeClass::String,
+                    _ => {
+                        self.report("TypeError", format!("Cannot add a {left} and a {right}"));
+                        TypeClass::Unknown
+                    },
+                }
+            },
+
+            Node::Increment(increment) => self.check_unary(increment.get_operand(), "++", TypeClass::Numeric),
+            Node::Decrement(decrement) => self.check_unary(decrement.get_operand(), "--", TypeClass::Numeric),
+            Node::Not(not) => self.check_unary(not.get_operand(), "!", TypeClass::Bool),
+
+            Node::Convert(convert) => {
+                self.check_node(convert.get_left());
+                TypeClass::of_kind(&convert.get_right())
+            },
+
+            Node::TernaryOp(ternary) => {
+                self.check_condition(ternary.get_condition(), "ternary operation");
+                let true_class = self.check_node(ternary.get_true_expr());
+                let false_class = self.check_node(ternary.get_false_expr());
+                if true_class == false_class { true_class } else { TypeClass::Unknown }
+            },
+
+            Node::If(_if) => {
+                self.check_condition(_if.get_condition(), "if");
+                self.check_node(_if.get_body());
+                TypeClass::Null
+            },
+
+            Node::IfElse(if_else) => {
+                self.check_condition(if_else.get_condition(), "if");
+                self.check_node(if_else.get_if_body());
+                self.check_node(if_else.get_else_body());
+                TypeClass::Null
+            },
+
+            Node::While(_while) => {
+                self.check_condition(_while.get_condition(), "while");
+                self.check_node(_while.get_body());
+                TypeClass::Null
+            },
+
+            Node::TryCatch(try_catch) => {
+                let (name_slot, details_slot) = try_catch.get_binding_slots();
+                for index in [name_slot, details_slot].into_iter().flatten() {
+                    self.locals.insert(index, (TypeClass::String, false));
+                }
+
+                self.check_node(try_catch.get_try_body());
+                self.check_node(try_catch.get_catch_body());
+                if let Some(finally_body) = try_catch.get_finally_body() {
+                    self.check_node(finally_body);
+                }
+
+                TypeClass::Null
+            },
+
+            Node::Located(located) => {
+                self.locations.push(located.get_location().clone());
+                let class = self.check_node(located.get_node());
+                self.locations.pop();
+                class
+            },
+
+            Node::Break(_) | Node::Continue(_) | Node::BlankReturn(_) => TypeClass::Null,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use apica_common::values::string::ValueString;
+    use apica_common::values::u32::ValueU32;
+    use crate::nodes::literal::NodeLiteral;
+    use crate::nodes::var_const_call::NodeVarConstCall;
+    use crate::nodes::var_decl::NodeVarDecl;
+    use crate::systems::resolver::ResolverSystem;
+    use super::*;
+
+    fn declare(name: &str, kind: ApicaTypeBytecode, expression: Node) -> Node {
+        Node::VarDecl(Box::new(NodeVarDecl::init(name.to_string(), kind, expression)))
+    }
+
+    fn variable(name: &str) -> Node {
+        Node::VarConstCall(NodeVarConstCall::init(name.to_string()))
+    }
+
+    fn check(nodes: Vec<Node>) -> usize {
+        let mut root = NodeCompound::init(nodes);
+        let mut resolver = ResolverSystem::init();
+        resolver.resolve_entry(&mut root);
+        assert!(resolver.get_errors().is_empty());
+
+        let registry = BuiltinRegistry::init();
+        let mut checker = TypeCheckerSystem::init(resolver.get_globals(), &registry);
+        checker.check_entry(&root);
+        checker.get_errors().len()
+    }
+
+    #[test]
+    fn numbers_convert_implicitly_to_bool() {
+        let number = Node::Literal(NodeLiteral::init(Value::U32(ValueU32::init_with(1))));
+        assert_eq!(check(vec![
+            declare("amount", ApicaTypeBytecode::U32, number),
+            declare("enabled", ApicaTypeBytecode::Bool, variable("amount")),
+        ]), 0);
+
+        let text = Node::Literal(NodeLiteral::init(Value::String(ValueString::init_with(String::from("yes")))));
+        assert_eq!(check(vec![
+            declare("answer", ApicaTypeBytecode::String, text),
+            declare("enabled", ApicaTypeBytecode::Bool, variable("answer")),
+        ]), 1);
+    }
+}
\ No newline at end of file
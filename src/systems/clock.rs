@@ -0,0 +1,86 @@
+﻿use std::fmt::Write;
+use std::time::Instant;
+use apica_common::element::Element;
+use chrono::{DateTime, Local};
+use crate::utils::format;
+
+pub const DEFAULT_DATETIME_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+pub struct ClockSystem {
+    system_start: Instant,
+    app_start: Instant,
+    last_frame: Instant,
+    delta_time: f64,
+    elapsed_time: f64,
+    monotonic_millis: u64,
+    frame_count: u64,
+    local_time: DateTime<Local>,
+}
+
+impl ClockSystem {
+    pub fn init() -> ClockSystem {
+        let now = Instant::now();
+        ClockSystem {
+            system_start: now,
+            app_start: now,
+            last_frame: now,
+            delta_time: 0.0,
+            elapsed_time: 0.0,
+            monotonic_millis: 0,
+            frame_count: 0,
+            local_time: Local::now(),
+        }
+    }
+
+    pub fn start_app(&mut self) {
+        let now = Instant::now();
+        self.app_start = now;
+        self.last_frame = now;
+        self.delta_time = 0.0;
+        self.elapsed_time = 0.0;
+        self.frame_count = 0;
+        self.monotonic_millis = now.duration_since(self.system_start).as_millis() as u64;
+        self.local_time = Local::now();
+    }
+
+    pub fn begin_frame(&mut self) {
+        let now = Instant::now();
+        self.delta_time = now.duration_since(self.last_frame).as_secs_f64();
+        self.elapsed_time = now.duration_since(self.app_start).as_secs_f64();
+        self.monotonic_millis = now.duration_since(self.system_start).as_millis() as u64;
+        self.frame_count += 1;
+        self.last_frame = now;
+        self.local_time = Local::now();
+    }
+
+    pub fn get_delta_time(&self) -> f64 {
+        self.delta_time
+    }
+
+    pub fn get_elapsed_time(&self) -> f64 {
+        self.elapsed_time
+    }
+
+    pub fn get_monotonic_millis(&self) -> u64 {
+        self.monotonic_millis
+    }
+
+    pub fn get_frame_count(&self) -> u64 {
+        self.frame_count
+    }
+
+    pub fn get_date_time(&self, parameters: Vec<Element>) -> Option<String> {
+        let pattern = match parameters.len() {
+            0 => DEFAULT_DATETIME_FORMAT.to_string(),
+            1 => format::element_to_string(parameters.first().unwrap()),
+            _ => return None,
+        };
+
+        let mut result = String::new();
+        if write!(result, "{}", self.local_time.format(&pattern)).is_err() {
+            return None;
+        }
+
+        Some(result)
+    }
+}
\ No newline at end of file
@@ -0,0 +1,286 @@
+﻿use apica_common::bytecodes::{ApicaBuiltinFunctionBytecode, ApicaTypeBytecode};
+use apica_common::values::value::Value;
+use crate::nodes::compound::NodeCompound;
+use crate::nodes::located::SourceLocation;
+use crate::nodes::node::Node;
+use crate::nodes::slot::Slot;
+use crate::systems::evaluator::EvaluatorModifier;
+
+#[derive(Copy, Clone)]
+pub enum UnaryOperation {
+    Increment,
+    Decrement,
+    Not,
+}
+
+pub enum Instruction {
+    PushLiteral(Value),
+    PushNull,
+    PushController(u8),
+    Pop,
+
+    LoadCopy(Option<Slot>, String),
+    LoadReference(Option<Slot>, String),
+    Declare(Option<Slot>, String),
+
+    CheckConvert(ApicaTypeBytecode),
+    Convert(ApicaTypeBytecode),
+    Add,
+    Unary(UnaryOperation),
+    UnarySlot(UnaryOperation, Option<Slot>, String),
+    CallBuiltin(ApicaBuiltinFunctionBytecode, usize),
+    CallNamed(String, usize),
+
+    Jump(usize),
+    Branch(usize, usize),
+    JumpUnlessTrue(usize),
+    EnterLoop,
+    ExitLoop,
+
+    PushLoopHandler(usize),
+    PushTryHandler(usize),
+    PushFinallyHandler(usize),
+    PopHandler,
+    BindError(Option<usize>, Option<usize>),
+    PushPending,
+    EndFinally,
+
+    PushTrace(SourceLocation),
+    PopTrace,
+}
+
+pub struct Program {
+    instructions: Vec<Instruction>,
+}
+
+impl Program {
+    pub fn get_instructions(&self) -> &Vec<Instruction> {
+        &self.instructions
+    }
+}
+
+pub struct CompilerSystem {
+    instructions: Vec<Instruction>,
+}
+
+impl CompilerSystem {
+    pub fn init() -> CompilerSystem {
+        CompilerSystem { instructions: vec![] }
+    }
+
+    pub fn compile_entry(&mut self, root: &NodeCompound) -> Program {
+        self.instructions = vec![];
+        self.compile_compound(root, EvaluatorModifier::None);
+        Program { instructions: std::mem::take(&mut self.instructions) }
+    }
+
+    fn emit(&mut self, instruction: Instruction) -> usize {
+        self.instructions.push(instruction);
+        self.instructions.len() - 1
+    }
+
+    fn next_address(&self) -> usize {
+        self.instructions.len()
+    }
+
+    fn patch(&mut self, address: usize, target: usize) {
+        match &mut self.instructions[address] {
+            Instruction::Jump(jump_target) => *jump_target = target,
+            Instruction::JumpUnlessTrue(jump_target) => *jump_target = target,
+            Instruction::PushTryHandler(jump_target) => *jump_target = target,
+            Instruction::PushFinallyHandler(jump_target) => *jump_target = target,
+            _ => {},
+        }
+    }
+
+    fn compile_compound(&mut self, compound: &NodeCompound, mode: EvaluatorModifier) {
+        for node in compound.get_nodes() {
+            self.compile_node(node, mode);
+            self.emit(Instruction::Pop);
+        }
+
+        self.emit(Instruction::PushNull);
+    }
+
+    fn compile_node(&mut self, node: &Node, mode: EvaluatorModifier) {
+        match node {
+            Node::Compound(compound) => self.compile_compound(compound, mode),
+
+            Node::BuiltinFuncCall(builtin) => {
+                for parameter in builtin.get_parameters() {
+                    self.compile_node(parameter, mode | EvaluatorModifier::CopyCall);
+                }
+
+                self.emit(Instruction::CallBuiltin(*builtin.get_function_bytecode(), builtin.get_parameters().len()));
+            },
+
+            Node::NamedFuncCall(named) => {
+                for parameter in named.get_parameters() {
+                    self.compile_node(parameter, mode | EvaluatorModifier::CopyCall);
+                }
+
+                self.emit(Instruction::CallNamed(named.get_name().clone(), named.get_parameters().len()));
+            },
+
+            Node::Literal(literal) => {
+                self.emit(Instruction::PushLiteral(literal.get_value().clone()));
+            },
+
+            Node::GlobalScope(global_scope) => self.compile_node(global_scope.get_statement(), mode | EvaluatorModifier::Global),
+
+            Node::VarConstCall(vc_call) => {
+                if mode.contains(EvaluatorModifier::CopyCall) {
+                    self.emit(Instruction::LoadCopy(vc_call.get_slot(), vc_call.get_name().clone()));
+                } else {
+                    self.emit(Instruction::LoadReference(vc_call.get_slot(), vc_call.get_name().clone()));
+                }
+            },
+
+            Node::VarDecl(var_decl) => {
+                self.compile_node(var_decl.get_expression(), EvaluatorModifier::CopyCall);
+                self.emit(Instruction::CheckConvert(*var_decl.get_value_kind()));
+                self.emit(Instruction::Declare(var_decl.get_slot(), var_decl.get_name().clone()));
+            },
+
+            Node::ConstDecl(const_decl) => {
+                self.compile_node(const_decl.get_expression(), EvaluatorModifier::CopyCall);
+                self.emit(Instruction::CheckConvert(*const_decl.get_value_kind()));
+                self.emit(Instruction::Declare(const_decl.get_slot(), const_decl.get_name().clone()));
+            },
+
+            Node::Add(add) => {
+                self.compile_node(add.get_left(), mode | EvaluatorModifier::CopyCall);
+                self.compile_node(add.get_right(), mode | EvaluatorModifier::CopyCall);
+                self.emit(Instruction::Add);
+            },
+
+            Node::Increment(increment) => self.compile_unary(UnaryOperation::Increment, increment.get_operand(), mode),
+            Node::Decrement(decrement) => self.compile_unary(UnaryOperation::Decrement, decrement.get_operand(), mode),
+            Node::Not(not) => self.compile_unary(UnaryOperation::Not, not.get_operand(), mode),
+
+            Node::Convert(convert) => {
+                self.compile_node(convert.get_left(), mode | EvaluatorModifier::CopyCall);
+                self.emit(Instruction::Convert(convert.get_right()));
+            },
+
+            Node::TernaryOp(ternary) => {
+                self.compile_node(ternary.get_condition(), EvaluatorModifier::CopyCall);
+                self.emit(Instruction::CheckConvert(ApicaTypeBytecode::Bool));
+                let branch = self.emit(Instruction::Branch(0, 0));
+                self.compile_node(ternary.get_true_expr(), mode);
+                let jump_end = self.emit(Instruction::Jump(0));
+                let false_address = self.next_address();
+                self.compile_node(ternary.get_false_expr(), mode);
+                let end_address = self.next_address();
+
+                self.instructions[branch] = Instruction::Branch(false_address, end_address);
+                self.patch(jump_end, end_address);
+            },
+
+            Node::If(_if) => {
+                self.compile_node(_if.get_condition(), EvaluatorModifier::CopyCall);
+                self.emit(Instruction::CheckConvert(ApicaTypeBytecode::Bool));
+                let branch = self.emit(Instruction::Branch(0, 0));
+                self.compile_node(_if.get_body(), mode);
+                self.emit(Instruction::Pop);
+                let false_address = self.emit(Instruction::PushNull);
+                let end_address = self.next_address();
+
+                self.instructions[branch] = Instruction::Branch(false_address, end_address);
+            },
+
+            Node::IfElse(if_else) => {
+                self.compile_node(if_else.get_condition(), EvaluatorModifier::CopyCall);
+                self.emit(Instruction::CheckConvert(ApicaTypeBytecode::Bool));
+                let branch = self.emit(Instruction::Branch(0, 0));
+                self.compile_node(if_else.get_if_body(), mode);
+                self.emit(Instruction::Pop);
+                self.emit(Instruction::PushNull);
+                let jump_end = self.emit(Instruction::Jump(0));
+                let else_address = self.next_address();
+                self.compile_node(if_else.get_else_body(), mode);
+                self.emit(Instruction::Pop);
+                self.emit(Instruction::PushNull);
+                let end_address = self.next_address();
+
+                self.instructions[branch] = Instruction::Branch(else_address, end_address);
+                self.patch(jump_end, end_address);
+            },
+
+            Node::While(_while) => {
+                self.emit(Instruction::EnterLoop);
+                let condition_address = self.next_address();
+                self.compile_node(_while.get_condition(), EvaluatorModifier::CopyCall);
+                self.emit(Instruction::CheckConvert(ApicaTypeBytecode::Bool));
+                let jump_exit = self.emit(Instruction::JumpUnlessTrue(0));
+                self.emit(Instruction::PushLoopHandler(condition_address));
+                self.compile_node(_while.get_body(), mode);
+                self.emit(Instruction::Pop);
+                self.emit(Instruction::PopHandler);
+                self.emit(Instruction::Jump(condition_address));
+                let exit_address = self.emit(Instruction::ExitLoop);
+                self.emit(Instruction::PushNull);
+
+                self.patch(jump_exit, exit_address);
+            },
+
+            Node::TryCatch(try_catch) => {
+                let finally_handler = try_catch.get_finally_body().map(|_| self.emit(Instruction::PushFinallyHandler(0)));
+                let try_handler = self.emit(Instruction::PushTryHandler(0));
+                self.compile_node(try_catch.get_try_body(), mode);
+                self.emit(Instruction::Pop);
+                self.emit(Instruction::PopHandler);
+                let jump_after_catch = self.emit(Instruction::Jump(0));
+
+                let (name_slot, details_slot) = try_catch.get_binding_slots();
+                let catch_address = self.emit(Instruction::BindError(name_slot, details_slot));
+                self.compile_node(try_catch.get_catch_body(), mode);
+                self.emit(Instruction::Pop);
+                let after_catch_address = self.next_address();
+
+                self.patch(try_handler, catch_address);
+                self.patch(jump_after_catch, after_catch_address);
+
+                if let (Some(finally_handler), Some(finally_body)) = (finally_handler, try_catch.get_finally_body()) {
+                    self.emit(Instruction::PopHandler);
+                    self.emit(Instruction::PushPending);
+                    let finally_address = self.next_address();
+                    self.compile_node(finally_body, mode);
+                    self.emit(Instruction::Pop);
+                    self.emit(Instruction::EndFinally);
+
+                    self.patch(finally_handler, finally_address);
+                }
+
+                self.emit(Instruction::PushNull);
+            },
+
+            Node::Break(_) => {
+                self.emit(Instruction::PushController(1));
+            },
+
+            Node::Continue(_) => {
+                self.emit(Instruction::PushController(2));
+            },
+
+            Node::BlankReturn(_) => {
+                self.emit(Instruction::PushController(0));
+            },
+
+            Node::Located(located) => {
+                self.emit(Instruction::PushTrace(located.get_location().clone()));
+                self.compile_node(located.get_node(), mode);
+                self.emit(Instruction::PopTrace);
+            },
+        }
+    }
+
+    fn compile_unary(&mut self, operation: UnaryOperation, operand: &Node, mode: EvaluatorModifier) {
+        if let Some(vc_call) = operand.as_variable() {
+            self.emit(Instruction::UnarySlot(operation, vc_call.get_slot(), vc_call.get_name().clone()));
+        } else {
+            self.compile_node(operand, mode - EvaluatorModifier::CopyCall);
+            self.emit(Instruction::Unary(operation));
+        }
+    }
+}
\ No newline at end of file
@@ -1,12 +1,13 @@
-﻿use apica_common::bytecodes::{ApicaBuiltinFunctionBytecode, ApicaTypeBytecode};
-use apica_common::context::Context;
+﻿use std::fmt::{Display, Formatter};
+use apica_common::bytecodes::{ApicaBuiltinFunctionBytecode, ApicaEntrypointBytecode, ApicaTypeBytecode};
 use apica_common::element::{Element, ElementModifier};
-use apica_common::values::bool::ValueBool;
 use apica_common::values::error::ValueError;
-use apica_common::values::pointer::ValuePointer;
+use apica_common::values::string::ValueString;
 use apica_common::values::u8::ValueU8;
 use apica_common::values::value::Value;
 use bitflags::bitflags;
+use crate::builtins::host::HostServices;
+use crate::builtins::registry::{self, BuiltinHandler, BuiltinRegistry};
 use crate::nodes::_break::NodeBreak;
 use crate::nodes::_continue::NodeContinue;
 use crate::nodes::_if::NodeIf;
@@ -22,15 +23,18 @@ use crate::nodes::global_scope::NodeGlobalScope;
 use crate::nodes::if_else::NodeIfElse;
 use crate::nodes::increment::NodeIncrement;
 use crate::nodes::literal::NodeLiteral;
+use crate::nodes::located::{NodeLocated, SourceLocation};
+use crate::nodes::named_func_call::NodeNamedFuncCall;
 use crate::nodes::node::Node;
 use crate::nodes::not::NodeNot;
+use crate::nodes::slot::Slot;
 use crate::nodes::ternary_op::NodeTernaryOp;
+use crate::nodes::try_catch::NodeTryCatch;
 use crate::nodes::var_const_call::NodeVarConstCall;
 use crate::nodes::var_decl::NodeVarDecl;
-use crate::systems::inputs::InputsSystem;
+use crate::systems::compiler::{Instruction, Program, UnaryOperation};
 use crate::systems::logger::LoggerSystem;
-use crate::systems::rights::RightSystem;
-use crate::systems::window::WindowSystem;
+use crate::systems::random::RandomSystem;
 
 bitflags! {
     #[derive(Copy, Clone)]
@@ -41,23 +45,189 @@ bitflags! {
     }
 }
 
+#[derive(Clone)]
+pub enum TraceFrame {
+    Entrypoint(ApicaEntrypointBytecode),
+    Builtin(ApicaBuiltinFunctionBytecode),
+    NamedBuiltin(String),
+    Location(SourceLocation),
+}
+
+impl Display for TraceFrame {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TraceFrame::Entrypoint(entry) => write!(f, "in entrypoint {:?}", entry),
+            TraceFrame::Builtin(function) => write!(f, "in builtin {:?}", function),
+            TraceFrame::NamedBuiltin(name) => write!(f, "in builtin {name}"),
+            TraceFrame::Location(location) => write!(f, "at {location}"),
+        }
+    }
+}
+
+enum HandlerKind {
+    Loop,
+    Try,
+    Finally,
+}
+
+struct Handler {
+    kind: HandlerKind,
+    target: usize,
+    stack_length: usize,
+    pending_length: usize,
+    trace_length: usize,
+    loops_length: usize,
+}
+
+struct MachineState {
+    stack: Vec<Element>,
+    handlers: Vec<Handler>,
+    pending: Vec<Option<Element>>,
+    caught: Option<Element>,
+}
+
+impl MachineState {
+    fn init() -> MachineState {
+        MachineState { stack: vec![], handlers: vec![], pending: vec![], caught: None }
+    }
+
+    fn pop_element(&mut self) -> Element {
+        self.stack.pop().unwrap_or_else(Element::create_null)
+    }
+
+    fn push_handler(&mut self, kind: HandlerKind, target: usize, trace_length: usize, loops_length: usize) {
+        self.handlers.push(Handler {
+            kind,
+            target,
+            stack_length: self.stack.len(),
+            pending_length: self.pending.len(),
+            trace_length,
+            loops_length,
+        });
+    }
+}
+
+pub const DEFAULT_EXECUTION_BUDGET: u64 = 10_000_000;
+pub const DEFAULT_EVALUATION_DEPTH: u32 = 512;
+pub const DEFAULT_MEMORY_QUOTA: u64 = 64 * 1024 * 1024;
+
 pub struct EvaluatorSystem {
-    context: Context,
+    globals: Vec<Option<Element>>,
+    locals: Vec<Option<Element>>,
+    registry: BuiltinRegistry,
+    trace: Vec<TraceFrame>,
+    error_trace: Option<Vec<TraceFrame>>,
+    loops: Vec<Option<SourceLocation>>,
+    budget: u64,
+    steps: u64,
+    depth: u32,
+    max_depth: u32,
+    memory_quota: u64,
+    global_memory: u64,
+    local_memory: u64,
+    peak_memory: u64,
 }
 
 impl EvaluatorSystem {
     pub fn init() -> EvaluatorSystem {
-        EvaluatorSystem { context: Context::init() }
+        EvaluatorSystem {
+            globals: vec![],
+            locals: vec![],
+            registry: BuiltinRegistry::init(),
+            trace: vec![],
+            error_trace: None,
+            loops: vec![],
+            budget: DEFAULT_EXECUTION_BUDGET,
+            steps: 0,
+            depth: 0,
+            max_depth: DEFAULT_EVALUATION_DEPTH,
+            memory_quota: DEFAULT_MEMORY_QUOTA,
+            global_memory: 0,
+            local_memory: 0,
+            peak_memory: 0,
+        }
+    }
+
+    pub fn set_max_depth(&mut self, max_depth: u32) {
+        self.max_depth = max_depth;
+    }
+
+    pub fn set_budget(&mut self, budget: u64) {
+        self.budget = budget;
+    }
+
+    pub fn has_timed_out(&self) -> bool {
+        self.steps > self.budget
+    }
+
+    pub fn set_memory_quota(&mut self, memory_quota: u64) {
+        self.memory_quota = memory_quota;
+    }
+
+    pub fn get_peak_memory(&self) -> u64 {
+        self.peak_memory
+    }
+
+    pub fn clear_data(&mut self, random: &mut RandomSystem) {
+        random.reset();
+        self.globals.clear();
+        self.locals.clear();
+        self.global_memory = 0;
+        self.local_memory = 0;
+        self.peak_memory = 0;
+    }
+
+    pub fn get_registry(&self) -> &BuiltinRegistry {
+        &self.registry
+    }
+
+    pub fn get_registry_mut(&mut self) -> &mut BuiltinRegistry {
+        &mut self.registry
+    }
+
+    pub fn prepare_globals(&mut self, count: usize) {
+        self.globals.resize_with(count, || None);
+    }
+
+    pub fn swap_globals(&mut self, globals: &mut Vec<Option<Element>>) {
+        std::mem::swap(&mut self.globals, globals);
+        self.global_memory = measure_slots(&self.globals);
     }
 
-    pub fn clear_data(&mut self) {
-        self.context = Context::init();
+    pub fn remap_globals(&mut self, mapping: &[Option<usize>]) -> usize {
+        let mut previous = std::mem::take(&mut self.globals);
+        self.globals = mapping.iter()
+            .map(|index| index.and_then(|index| previous.get_mut(index).and_then(Option::take)))
+            .collect();
+
+        self.global_memory = measure_slots(&self.globals);
+        self.globals.iter().filter(|global| global.is_some()).count()
+    }
+
+    pub fn evaluate(&mut self, entry: ApicaEntrypointBytecode, root: &NodeCompound, host: &mut HostServices) {
+        self.begin_entry(entry);
+        let result = self.evaluate_compound(root, EvaluatorModifier::None, host);
+        self.report_result(result, host.get_logger());
     }
 
-    pub fn evaluate(&mut self, root: &NodeCompound, 
-                    logger: &mut LoggerSystem, rights: &mut RightSystem, window: &mut WindowSystem, 
-                    inputs: &mut InputsSystem) {
-        let result = self.evaluate_compound(root, EvaluatorModifier::None, logger, rights, window, inputs);
+    pub fn execute(&mut self, entry: ApicaEntrypointBytecode, program: &Program, host: &mut HostServices) {
+        self.begin_entry(entry);
+        let result = self.run_program(program, host);
+        self.report_result(result, host.get_logger());
+    }
+
+    fn begin_entry(&mut self, entry: ApicaEntrypointBytecode) {
+        self.trace.clear();
+        self.trace.push(TraceFrame::Entrypoint(entry));
+        self.error_trace = None;
+        self.loops.clear();
+        self.locals.clear();
+        self.local_memory = 0;
+        self.steps = 0;
+        self.depth = 0;
+    }
+
+    fn report_result(&mut self, result: Element, logger: &mut LoggerSystem) {
         if result.get_modifier().contains(ElementModifier::Error) {
             if let Value::Error(value_error) = result.get_value() {
                 if let Some(name) = value_error.get_name() {
@@ -68,6 +238,11 @@ impl EvaluatorSystem {
                     }
                 }
             }
+
+            let trace = self.error_trace.take().unwrap_or_else(|| self.trace.clone());
+            for frame in trace.iter().rev() {
+                logger.system_logn_error(format!("    {frame}"));
+            }
         } else if result.get_modifier().contains(ElementModifier::Controller) {
             if let Value::U8(value) = result.get_value() {
                 if let Some(value_u8) = value.get_value() {
@@ -85,56 +260,386 @@ impl EvaluatorSystem {
         }
     }
 
-    fn evaluate_node(&mut self, node: &Node, mode: EvaluatorModifier,
-                     logger: &mut LoggerSystem, rights: &mut RightSystem, window: &mut WindowSystem, 
-                     inputs: &mut InputsSystem) -> Element {
-        match node {
-            Node::Compound(compound) => self.evaluate_compound(compound, mode, logger, rights, window, inputs),
-            Node::BuiltinFuncCall(builtin) => self.evaluate_builtin_func_call(builtin, mode, logger, rights, window, inputs),
+    fn run_program(&mut self, program: &Program, host: &mut HostServices) -> Element {
+        let instructions = program.get_instructions();
+        let mut machine = MachineState::init();
+        let mut address = 0;
+
+        while address < instructions.len() {
+            // Source locations are bookkeeping, so they do not count against the step budget.
+            let is_trace = matches!(instructions[address], Instruction::PushTrace(_) | Instruction::PopTrace);
+            if !is_trace {
+                self.steps += 1;
+            }
+
+            if self.steps > self.budget {
+                let error = self.timeout_error();
+                match self.unwind(&mut machine, error) {
+                    Ok(target) => {
+                        address = target;
+                        continue;
+                    },
+                    Err(result) => return result,
+                }
+            }
+
+            let mut next_address = address + 1;
+            let produced = match &instructions[address] {
+                Instruction::PushLiteral(value) => Some(Element::init(ElementModifier::None, value.clone())),
+                Instruction::PushNull => Some(Element::create_null()),
+                Instruction::PushController(controller) => Some(Element::init(
+                    ElementModifier::Controller,
+                    Value::U8(ValueU8::init_with(*controller))
+                )),
+
+                Instruction::Pop => {
+                    machine.stack.pop();
+                    None
+                },
+
+                Instruction::LoadCopy(slot, name) => Some(self.load_element(*slot, name, true)),
+                Instruction::LoadReference(slot, name) => Some(self.load_element(*slot, name, false)),
+                Instruction::Declare(slot, name) => {
+                    let element = machine.pop_element();
+                    Some(self.declare_element(*slot, name, element))
+                },
+
+                Instruction::CheckConvert(kind) => Some(machine.pop_element().check_convert(*kind)),
+                Instruction::Convert(kind) => Some(machine.pop_element().convert(*kind)),
+                Instruction::Add => {
+                    let right = machine.pop_element();
+                    let left = machine.pop_element();
+                    Some(self.check_memory(left.add(&right)))
+                },
+
+                Instruction::Unary(operation) => {
+                    let mut operand = machine.pop_element();
+                    Some(Self::apply_unary(*operation, &mut operand))
+                },
+
+                Instruction::UnarySlot(operation, slot, name) => Some(self.apply_unary_slot(*operation, *slot, name)),
+
+                Instruction::CallBuiltin(function, count) => {
+                    let parameters = machine.stack.split_off(machine.stack.len().saturating_sub(*count));
+                    self.trace.push(TraceFrame::Builtin(*function));
+                    let result = self.call_builtin(function, parameters, host);
+                    if result.get_modifier().contains(ElementModifier::Error) {
+                        self.capture_trace();
+                    }
+
+                    self.trace.pop();
+                    Some(result)
+                },
+
+                Instruction::CallNamed(name, count) => {
+                    let parameters = machine.stack.split_off(machine.stack.len().saturating_sub(*count));
+                    self.trace.push(TraceFrame::NamedBuiltin(name.clone()));
+                    let result = self.call_named(name, parameters, host);
+                    if result.get_modifier().contains(ElementModifier::Error) {
+                        self.capture_trace();
+                    }
+
+                    self.trace.pop();
+                    Some(result)
+                },
+
+                Instruction::Jump(target) => {
+                    next_address = *target;
+                    None
+                },
+
+                Instruction::Branch(false_target, other_target) => {
+                    let condition = machine.pop_element();
+                    if let Value::Bool(result) = condition.get_value() {
+                        if result.get_value() != Some(true) {
+                            next_address = *false_target;
+                        }
+
+                        None
+                    } else {
+                        next_address = *other_target;
+                        Some(condition)
+                    }
+                },
+
+                Instruction::JumpUnlessTrue(target) => {
+                    let condition = machine.pop_element();
+                    let is_true = matches!(condition.get_value(), Value::Bool(result) if result.get_value() == Some(true));
+                    if !is_true {
+                        next_address = *target;
+                    }
+
+                    None
+                },
+
+                Instruction::EnterLoop => {
+                    let location = self.trace.iter().rev().find_map(|frame| match frame {
+                        TraceFrame::Location(location) => Some(location.clone()),
+                        _ => None,
+                    });
+                    self.loops.push(location);
+                    None
+                },
+
+                Instruction::ExitLoop => {
+                    self.loops.pop();
+                    None
+                },
+
+                Instruction::PushLoopHandler(target) => {
+                    machine.push_handler(HandlerKind::Loop, *target, self.trace.len(), self.loops.len());
+                    None
+                },
+
+                Instruction::PushTryHandler(target) => {
+                    machine.push_handler(HandlerKind::Try, *target, self.trace.len(), self.loops.len());
+                    None
+                },
+
+                Instruction::PushFinallyHandler(target) => {
+                    machine.push_handler(HandlerKind::Finally, *target, self.trace.len(), self.loops.len());
+                    None
+                },
+
+                Instruction::PopHandler => {
+                    machine.handlers.pop();
+                    None
+                },
+
+                Instruction::BindError(name_slot, details_slot) => {
+                    let (error_name, error_details) = match machine.caught.take() {
+                        Some(caught) => Self::error_parts(&caught),
+                        None => (String::from("Error"), String::new()),
+                    };
+
+                    self.bind_error(*name_slot, *details_slot, error_name, error_details);
+                    None
+                },
+
+                Instruction::PushPending => {
+                    machine.pending.push(None);
+                    None
+                },
+
+                Instruction::EndFinally => machine.pending.pop().flatten(),
+
+                Instruction::PushTrace(location) => {
+                    self.trace.push(TraceFrame::Location(location.clone()));
+                    None
+                },
+
+                Instruction::PopTrace => {
+                    self.trace.pop();
+                    None
+                },
+            };
+
+            if let Some(element) = produced {
+                if element.is_error_or_controller() {
+                    if element.get_modifier().contains(ElementModifier::Error) {
+                        self.capture_trace();
+                    }
+
+                    match self.unwind(&mut machine, element) {
+                        Ok(target) => next_address = target,
+                        Err(result) => return result,
+                    }
+                } else {
+                    machine.stack.push(element);
+                }
+            }
+
+            address = next_address;
+        }
+
+        Element::create_null()
+    }
+
+    fn unwind(&mut self, machine: &mut MachineState, element: Element) -> Result<usize, Element> {
+        while let Some(handler) = machine.handlers.pop() {
+            let is_error = element.get_modifier().contains(ElementModifier::Error);
+            let catches = match handler.kind {
+                HandlerKind::Loop => !is_error,
+                HandlerKind::Try => is_error,
+                HandlerKind::Finally => true,
+            };
+
+            if !catches {
+                continue;
+            }
+
+            machine.stack.truncate(handler.stack_length);
+            machine.pending.truncate(handler.pending_length);
+            self.trace.truncate(handler.trace_length);
+            self.loops.truncate(handler.loops_length);
+
+            match handler.kind {
+                HandlerKind::Loop => {},
+                HandlerKind::Try => {
+                    self.error_trace = None;
+                    machine.caught = Some(element);
+                },
+                HandlerKind::Finally => machine.pending.push(Some(element)),
+            }
+
+            return Ok(handler.target);
+        }
+
+        Err(element)
+    }
+
+    fn error_parts(element: &Element) -> (String, String) {
+        if let Value::Error(value_error) = element.get_value() {
+            (
+                value_error.get_name().as_ref().map(|name| name.to_string()).unwrap_or(String::from("Error")),
+                value_error.get_details().as_ref().map(|details| details.to_string()).unwrap_or_default(),
+            )
+        } else {
+            (String::from("Error"), String::new())
+        }
+    }
+
+    fn bind_error(&mut self, name_slot: Option<usize>, details_slot: Option<usize>, error_name: String, error_details: String) {
+        for (slot, value) in [(name_slot, error_name), (details_slot, error_details)] {
+            if let Some(index) = slot {
+                let element = Element::init(ElementModifier::None, Value::String(ValueString::init_with(value)));
+                self.store_slot(Slot::Local(index), element);
+            }
+        }
+    }
+
+    fn store_slot(&mut self, slot: Slot, element: Element) -> Element {
+        let size = measure_element(&element);
+        let previous = self.slot_element(Some(slot)).map_or(0, measure_element);
+        let used = (self.global_memory + self.local_memory).saturating_sub(previous);
+        if let Some(error) = self.reserve_memory(used, size) {
+            return error;
+        }
+
+        let (storage, index, memory) = match slot {
+            Slot::Local(index) => (&mut self.locals, index, &mut self.local_memory),
+            Slot::Global(index) => (&mut self.globals, index, &mut self.global_memory),
+        };
+
+        *memory = memory.saturating_sub(previous) + size;
+        if index >= storage.len() {
+            storage.resize_with(index + 1, || None);
+        }
+
+        storage[index] = Some(element);
+        Element::create_null()
+    }
+
+    fn check_memory(&mut self, element: Element) -> Element {
+        if element.is_error_or_controller() {
+            return element;
+        }
+
+        match self.reserve_memory(self.global_memory + self.local_memory, measure_element(&element)) {
+            Some(error) => error,
+            None => element,
+        }
+    }
+
+    fn reserve_memory(&mut self, used: u64, size: u64) -> Option<Element> {
+        if used + size > self.memory_quota {
+            self.capture_trace();
+            return Some(Element::create_error(Value::Error(ValueError::init_with(
+                String::from("MemoryError"),
+                Some(format!("The memory quota of {} bytes would be exceeded ({} bytes)", self.memory_quota, used + size)),
+            ))));
+        }
+
+        self.peak_memory = self.peak_memory.max(used + size);
+        None
+    }
+
+    fn capture_trace(&mut self) {
+        if self.error_trace.is_none() {
+            self.error_trace = Some(self.trace.clone());
+        }
+    }
+
+    fn timeout_error(&mut self) -> Element {
+        self.capture_trace();
+        let details = match self.loops.last() {
+            Some(Some(location)) => format!("The execution budget of {} nodes was exceeded in the while loop near {location}", self.budget),
+            Some(None) => format!("The execution budget of {} nodes was exceeded in a while loop (nesting depth {})", self.budget, self.loops.len()),
+            None => format!("The execution budget of {} nodes was exceeded", self.budget),
+        };
+
+        Element::create_error(Value::Error(ValueError::init_with(
+            String::from("TimeoutError"),
+            Some(details),
+        )))
+    }
+
+    fn evaluate_node(&mut self, node: &Node, mode: EvaluatorModifier, host: &mut HostServices) -> Element {
+        // Source locations are bookkeeping, so they count neither as a step nor as a nesting level.
+        if let Node::Located(located) = node {
+            return self.evaluate_located(located, mode, host);
+        }
+
+        self.steps += 1;
+        if self.steps > self.budget {
+            return self.timeout_error();
+        }
+
+        if self.depth >= self.max_depth {
+            self.capture_trace();
+            return Element::create_error(Value::Error(ValueError::init_with(
+                String::from("StackOverflowError"),
+                Some(format!("The maximum evaluation depth of {} nested nodes was exceeded", self.max_depth)),
+            )));
+        }
+
+        self.depth += 1;
+        let result = match node {
+            Node::Compound(compound) => self.evaluate_compound(compound, mode, host),
+            Node::BuiltinFuncCall(builtin) => self.evaluate_builtin_func_call(builtin, mode, host),
+            Node::NamedFuncCall(named) => self.evaluate_named_func_call(named, mode, host),
             Node::Literal(literal) => self.evaluate_literal(literal),
-            Node::GlobalScope(global_scope) => self.evaluate_global_scope(global_scope, mode, logger, rights, window, inputs),
+            Node::GlobalScope(global_scope) => self.evaluate_global_scope(global_scope, mode, host),
             Node::VarConstCall(vc_call) => self.evaluate_var_const_call(vc_call, mode),
-            Node::VarDecl(var_decl) => self.evaluate_var_decl(var_decl, mode, logger, rights, window, inputs),
-            Node::ConstDecl(const_decl) => self.evaluate_const_decl(const_decl, mode, logger, rights, window, inputs),
-            Node::Add(add) => self.evaluate_add(add, mode, logger, rights, window, inputs),
-            Node::Increment(increment) => self.evaluate_increment(increment, mode, logger, rights, window, inputs),
-            Node::Decrement(decrement) => self.evaluate_decrement(decrement, mode, logger, rights, window, inputs),
-            Node::Not(not) => self.evaluate_not(not, mode, logger, rights, window, inputs),
-            Node::Convert(convert) => self.evaluate_convert(convert, mode, logger, rights, window, inputs),
-            Node::TernaryOp(ternary) => self.evaluate_ternary_operation(ternary, mode, logger, rights, window, inputs),
-            Node::If(_if) => self.evaluate_if(_if, mode, logger, rights, window, inputs),
-            Node::IfElse(if_else) => self.evaluate_if_else(if_else, mode, logger, rights, window, inputs),
-            Node::While(_while) => self.evaluate_while(_while, mode, logger, rights, window, inputs),
+            Node::VarDecl(var_decl) => self.evaluate_var_decl(var_decl, host),
+            Node::ConstDecl(const_decl) => self.evaluate_const_decl(const_decl, host),
+            Node::Add(add) => self.evaluate_add(add, mode, host),
+            Node::Increment(increment) => self.evaluate_increment(increment, mode, host),
+            Node::Decrement(decrement) => self.evaluate_decrement(decrement, mode, host),
+            Node::Not(not) => self.evaluate_not(not, mode, host),
+            Node::Convert(convert) => self.evaluate_convert(convert, mode, host),
+            Node::TernaryOp(ternary) => self.evaluate_ternary_operation(ternary, mode, host),
+            Node::If(_if) => self.evaluate_if(_if, mode, host),
+            Node::IfElse(if_else) => self.evaluate_if_else(if_else, mode, host),
+            Node::While(_while) => self.evaluate_while(_while, mode, host),
+            Node::TryCatch(try_catch) => self.evaluate_try_catch(try_catch, mode, host),
             Node::Break(_break) => self.evaluate_break(_break),
             Node::Continue(_continue) => self.evaluate_continue(_continue),
             Node::BlankReturn(blank_return) => self.evaluate_blank_return(blank_return),
-        }
-    }
+            Node::Located(located) => self.evaluate_located(located, mode, host),
+        };
 
-    fn evaluate_compound(&mut self, root: &NodeCompound, mode: EvaluatorModifier,
-                         logger: &mut LoggerSystem, rights: &mut RightSystem, window: &mut WindowSystem, 
-                         inputs: &mut InputsSystem) -> Element {
-        self.context.push_scope();
+        self.depth -= 1;
+        result
+    }
 
+    fn evaluate_compound(&mut self, root: &NodeCompound, mode: EvaluatorModifier, host: &mut HostServices) -> Element {
         for node in root.get_nodes() {
-            let result = self.evaluate_node(node, mode, logger, rights, window, inputs);
+            let result = self.evaluate_node(node, mode, host);
             if result.is_error_or_controller() {
                 return result;
             }
         }
 
-        self.context.pop_scope();
         Element::create_null()
     }
 
-    fn evaluate_builtin_func_call(&mut self, builtin: &NodeBuiltinFuncCall, mode: EvaluatorModifier,
-                                  logger: &mut LoggerSystem, rights: &mut RightSystem, window: &mut WindowSystem, 
-                                  inputs: &mut InputsSystem) -> Element {
+    fn evaluate_builtin_func_call(&mut self, builtin: &NodeBuiltinFuncCall, mode: EvaluatorModifier, host: &mut HostServices) -> Element {
         let mut parameters = vec![];
 
         let new_mode = mode | EvaluatorModifier::CopyCall;
         for node in builtin.get_parameters() {
-            let result = self.evaluate_node(node, new_mode, logger, rights, window, inputs);
+            let result = self.evaluate_node(node, new_mode, host);
             if result.is_error_or_controller() {
                 return result;
             }
@@ -142,112 +647,93 @@ impl EvaluatorSystem {
             parameters.push(result);
         }
 
-        match builtin.get_function_bytecode() {
-            ApicaBuiltinFunctionBytecode::LogInfo => {
-                logger.log_info(parameters);
-                Element::create_null()
-            },
-
-            ApicaBuiltinFunctionBytecode::LognInfo => {
-                logger.logn_info(parameters);
-                Element::create_null()
-            },
+        self.trace.push(TraceFrame::Builtin(*builtin.get_function_bytecode()));
+        let result = self.call_builtin(builtin.get_function_bytecode(), parameters, host);
+        if result.get_modifier().contains(ElementModifier::Error) {
+            self.capture_trace();
+        }
 
-            ApicaBuiltinFunctionBytecode::LogSuccess => {
-                logger.log_success(parameters);
-                Element::create_null()
-            },
+        self.trace.pop();
+        result
+    }
 
-            ApicaBuiltinFunctionBytecode::LognSuccess => {
-                logger.logn_success(parameters);
-                Element::create_null()
-            },
+    fn evaluate_named_func_call(&mut self, named: &NodeNamedFuncCall, mode: EvaluatorModifier, host: &mut HostServices) -> Element {
+        let mut parameters = vec![];
 
-            ApicaBuiltinFunctionBytecode::LogWarning => {
-                logger.log_warning(parameters);
-                Element::create_null()
-            },
+        let new_mode = mode | EvaluatorModifier::CopyCall;
+        for node in named.get_parameters() {
+            let result = self.evaluate_node(node, new_mode, host);
+            if result.is_error_or_controller() {
+                return result;
+            }
 
-            ApicaBuiltinFunctionBytecode::LognWarning => {
-                logger.logn_warning(parameters);
-                Element::create_null()
-            },
+            parameters.push(result);
+        }
 
-            ApicaBuiltinFunctionBytecode::LogError => {
-                logger.log_error(parameters);
-                Element::create_null()
-            },
+        self.trace.push(TraceFrame::NamedBuiltin(named.get_name().clone()));
+        let result = self.call_named(named.get_name(), parameters, host);
+        if result.get_modifier().contains(ElementModifier::Error) {
+            self.capture_trace();
+        }
 
-            ApicaBuiltinFunctionBytecode::LognError => {
-                logger.logn_error(parameters);
-                Element::create_null()
-            },
+        self.trace.pop();
+        result
+    }
 
-            ApicaBuiltinFunctionBytecode::Quit => {
-                rights.quit_app();
-                Element::create_null()
-            },
+    fn call_builtin(&mut self, function: &ApicaBuiltinFunctionBytecode, parameters: Vec<Element>, host: &mut HostServices) -> Element {
+        let result = match self.registry.get(function) {
+            Some(handler) => Self::invoke_handler(handler, parameters, host),
+            None => Element::create_error(Value::Error(ValueError::init_with(
+                String::from("AccessError"),
+                Some(format!("An undefined builtin func-call was found -> {:?}", function)),
+            ))),
+        };
 
-            ApicaBuiltinFunctionBytecode::SetTitle => {
-                if !window.set_title(parameters) {
-                    Element::create_error(Value::Error(ValueError::init_with(
-                        String::from("ArgumentError"),
-                        Some(String::from("Incorrect arguments passed to the function `SetTitle`"))
-                    )))
-                } else {
-                    Element::create_null()
-                }
-            },
+        let result = self.apply_state_request(result, host);
+        self.check_memory(result)
+    }
 
-            ApicaBuiltinFunctionBytecode::SetResizable => {
-                if !window.set_resizable(parameters) {
-                    Element::create_error(Value::Error(ValueError::init_with(
-                        String::from("ArgumentError"),
-                        Some(String::from("Incorrect arguments passed to the function `SetResizable`"))
-                    )))
-                } else {
-                    Element::create_null()
-                }
-            },
+    fn call_named(&mut self, name: &str, parameters: Vec<Element>, host: &mut HostServices) -> Element {
+        let result = match self.registry.get_named(name) {
+            Some(handler) => Self::invoke_handler(handler, parameters, host),
+            None => Element::create_error(Value::Error(ValueError::init_with(
+                String::from("AccessError"),
+                Some(format!("An undefined named func-call was found -> {name}")),
+            ))),
+        };
 
-            ApicaBuiltinFunctionBytecode::IsKeyReleased => {
-                if let Some(state) = inputs.is_key_released(parameters) {
-                    Element::init(ElementModifier::None, Value::Bool(ValueBool::init_with(state)))
-                } else {
-                    Element::create_error(Value::Error(ValueError::init_with(
-                        String::from("ArgumentError"),
-                        Some(String::from("Incorrect arguments passed to the function `IsKeyJustPressed`"))
-                    )))
-                }
-            },
-            
-            ApicaBuiltinFunctionBytecode::IsKeyJustPressed => {
-                if let Some(state) = inputs.is_key_just_pressed(parameters) {
-                    Element::init(ElementModifier::None, Value::Bool(ValueBool::init_with(state)))
-                } else {
-                    Element::create_error(Value::Error(ValueError::init_with(
-                        String::from("ArgumentError"),
-                        Some(String::from("Incorrect arguments passed to the function `IsKeyJustPressed`"))
-                    )))
-                }
-            },
+        let result = self.apply_state_request(result, host);
+        self.check_memory(result)
+    }
 
-            ApicaBuiltinFunctionBytecode::IsKeyPressed => {
-                if let Some(state) = inputs.is_key_pressed(parameters) {
-                    Element::init(ElementModifier::None, Value::Bool(ValueBool::init_with(state)))
-                } else {
-                    Element::create_error(Value::Error(ValueError::init_with(
-                        String::from("ArgumentError"),
-                        Some(String::from("Incorrect arguments passed to the function `IsKeyPressed`"))
-                    )))
-                }
+    fn apply_state_request(&mut self, result: Element, host: &mut HostServices) -> Element {
+        match host.get_state().take_request() {
+            Some(request) => {
+                let result = host.get_state().execute(request, &mut self.globals);
+                self.global_memory = measure_slots(&self.globals);
+                result
             },
+            None => result,
+        }
+    }
 
-            _ => Element::create_error(Value::Error(ValueError::init_with(
+    fn invoke_handler(handler: &dyn BuiltinHandler, parameters: Vec<Element>, host: &mut HostServices) -> Element {
+        if let Some(right) = handler.get_required_right() && !host.get_rights().has_right(right) {
+            return Element::create_error(Value::Error(ValueError::init_with(
                 String::from("AccessError"),
-                Some(format!("An undefined builtin func-call was found -> {:?}", builtin.get_function_bytecode())),
-            ))),
+                Some(format!("The app does not have the right to call the function `{}`", handler.get_name())),
+            )));
+        }
+
+        if let Some(capability) = handler.get_required_capability() && let Some(error) = host.get_rights().check_capability(capability, handler.get_name()) {
+            return error;
+        }
+
+        if !registry::accepts_arity(handler, parameters.len()) {
+            return registry::argument_error(handler.get_name());
         }
+
+        handler.call(parameters, host)
     }
 
     fn evaluate_literal(&mut self, literal: &NodeLiteral) -> Element {
@@ -255,165 +741,154 @@ impl EvaluatorSystem {
         Element::init(ElementModifier::None, value)
     }
 
-    fn evaluate_global_scope(&mut self, global: &NodeGlobalScope, mode: EvaluatorModifier,
-                             logger: &mut LoggerSystem, rights: &mut RightSystem, window: &mut WindowSystem, 
-                             inputs: &mut InputsSystem) -> Element {
-        self.evaluate_node(global.get_statement(), mode | EvaluatorModifier::Global, logger, rights, window, inputs)
+    fn evaluate_global_scope(&mut self, global: &NodeGlobalScope, mode: EvaluatorModifier, host: &mut HostServices) -> Element {
+        self.evaluate_node(global.get_statement(), mode | EvaluatorModifier::Global, host)
     }
 
     fn evaluate_var_const_call(&mut self, vc_call: &NodeVarConstCall, mode: EvaluatorModifier) -> Element {
-        if let Some(vc_element) = self.context.get_element(vc_call.get_name(), mode.contains(EvaluatorModifier::Global)) {
-            if mode.contains(EvaluatorModifier::CopyCall) {
+        self.load_element(vc_call.get_slot(), vc_call.get_name(), mode.contains(EvaluatorModifier::CopyCall))
+    }
+
+    fn slot_element(&self, slot: Option<Slot>) -> Option<&Element> {
+        match slot? {
+            Slot::Local(index) => self.locals.get(index)?.as_ref(),
+            Slot::Global(index) => self.globals.get(index)?.as_ref(),
+        }
+    }
+
+    fn slot_element_mut(&mut self, slot: Option<Slot>) -> Option<&mut Element> {
+        match slot? {
+            Slot::Local(index) => self.locals.get_mut(index)?.as_mut(),
+            Slot::Global(index) => self.globals.get_mut(index)?.as_mut(),
+        }
+    }
+
+    fn load_element(&self, slot: Option<Slot>, name: &String, copy: bool) -> Element {
+        if let Some(vc_element) = self.slot_element(slot) {
+            if copy {
                 Element::init(ElementModifier::None, vc_element.get_value().clone())
             } else {
-                Element::init(vc_element.get_modifier(), Value::Pointer(
-                    ValuePointer::init_with(vc_call.get_name().clone(), mode.contains(EvaluatorModifier::Global)),
-                ))
+                Element::init(vc_element.get_modifier(), vc_element.get_value().clone())
             }
         } else {
             Element::create_error(Value::Error(ValueError::init_with(
                 String::from("AccessError"),
-                Some(format!("Cannot find a reference to a var/const -> {}", vc_call.get_name())),
+                Some(format!("Cannot find a reference to a var/const -> {}", name)),
             )))   
         }
     }
 
-    fn evaluate_var_decl(&mut self, var_decl: &NodeVarDecl, mode: EvaluatorModifier,
-                         logger: &mut LoggerSystem, rights: &mut RightSystem, window: &mut WindowSystem, 
-                         inputs: &mut InputsSystem) -> Element {
-        let result = self.evaluate_node(var_decl.get_expression(), EvaluatorModifier::CopyCall, logger, rights, window, inputs).check_convert(*var_decl.get_value_kind());
+    fn evaluate_var_decl(&mut self, var_decl: &NodeVarDecl, host: &mut HostServices) -> Element {
+        let result = self.evaluate_node(var_decl.get_expression(), EvaluatorModifier::CopyCall, host).check_convert(*var_decl.get_value_kind());
         if result.is_error_or_controller() {
             return result;
         }
 
-        if self.context.set_element(var_decl.get_name().clone(), result, mode.contains(EvaluatorModifier::Global)) {
-            Element::create_null()
-        } else {
-            Element::create_error(Value::Error(ValueError::init_with(
-                String::from("DeclarationError"),
-                Some(format!("An element with this name already exists -> {}", var_decl.get_name())),
-            )))
-        }
+        self.declare_element(var_decl.get_slot(), var_decl.get_name(), result)
     }
 
-    fn evaluate_const_decl(&mut self, const_decl: &NodeConstDecl, mode: EvaluatorModifier,
-                           logger: &mut LoggerSystem, rights: &mut RightSystem, window: &mut WindowSystem, 
-                           inputs: &mut InputsSystem) -> Element {
-        let result = self.evaluate_node(const_decl.get_expression(), EvaluatorModifier::CopyCall, logger, rights, window, inputs).check_convert(*const_decl.get_value_kind());
+    fn evaluate_const_decl(&mut self, const_decl: &NodeConstDecl, host: &mut HostServices) -> Element {
+        let result = self.evaluate_node(const_decl.get_expression(), EvaluatorModifier::CopyCall, host).check_convert(*const_decl.get_value_kind());
         if result.is_error_or_controller() {
             return result;
         }
 
-        if self.context.set_element(const_decl.get_name().clone(), result, mode.contains(EvaluatorModifier::Global)) {
-            Element::create_null()
-        } else {
-            Element::create_error(Value::Error(ValueError::init_with(
+        self.declare_element(const_decl.get_slot(), const_decl.get_name(), result)
+    }
+
+    fn declare_element(&mut self, slot: Option<Slot>, name: &String, element: Element) -> Element {
+        match slot {
+            Some(Slot::Local(index)) => self.store_slot(Slot::Local(index), element),
+            Some(Slot::Global(index)) => {
+                if self.globals.get(index).is_some_and(|global| global.is_some()) {
+                    return Element::create_error(Value::Error(ValueError::init_with(
+                        String::from("DeclarationError"),
+                        Some(format!("An element with this name already exists -> {}", name)),
+                    )));
+                }
+
+                self.store_slot(Slot::Global(index), element)
+            },
+            None => Element::create_error(Value::Error(ValueError::init_with(
                 String::from("DeclarationError"),
-                Some(format!("An element with this name already exists -> {}", const_decl.get_name())),
-            )))
+                Some(format!("The declaration was not resolved to a slot -> {}", name)),
+            ))),
         }
     }
 
-    fn evaluate_add(&mut self, add: &NodeAdd, mode: EvaluatorModifier, 
-                    logger: &mut LoggerSystem, rights: &mut RightSystem, window: &mut WindowSystem, 
-                    inputs: &mut InputsSystem) -> Element {
-        let left = self.evaluate_node(add.get_left(), mode | EvaluatorModifier::CopyCall, logger, rights, window, inputs);
+    fn evaluate_add(&mut self, add: &NodeAdd, mode: EvaluatorModifier, host: &mut HostServices) -> Element {
+        let left = self.evaluate_node(add.get_left(), mode | EvaluatorModifier::CopyCall, host);
         if left.is_error_or_controller() {
             return left;
         }
         
-        let right = self.evaluate_node(add.get_right(), mode | EvaluatorModifier::CopyCall, logger, rights, window, inputs);
+        let right = self.evaluate_node(add.get_right(), mode | EvaluatorModifier::CopyCall, host);
         if right.is_error_or_controller() {
             return right;
         }
         
-        left.add(&right)
+        let result = left.add(&right);
+        self.check_memory(result)
     }
     
-    fn evaluate_increment(&mut self, increment: &NodeIncrement, mode: EvaluatorModifier,
-                          logger: &mut LoggerSystem, rights: &mut RightSystem, window: &mut WindowSystem, 
-                          inputs: &mut InputsSystem) -> Element {
-        let mut operand = self.evaluate_node(increment.get_operand(), mode - EvaluatorModifier::CopyCall, logger, rights, window, inputs);
+    fn evaluate_increment(&mut self, increment: &NodeIncrement, mode: EvaluatorModifier, host: &mut HostServices) -> Element {
+        self.evaluate_unary(UnaryOperation::Increment, increment.get_operand(), mode, host)
+    }
+
+    fn evaluate_decrement(&mut self, decrement: &NodeDecrement, mode: EvaluatorModifier, host: &mut HostServices) -> Element {
+        self.evaluate_unary(UnaryOperation::Decrement, decrement.get_operand(), mode, host)
+    }
+
+    fn evaluate_not(&mut self, not: &NodeNot, mode: EvaluatorModifier, host: &mut HostServices) -> Element {
+        self.evaluate_unary(UnaryOperation::Not, not.get_operand(), mode, host)
+    }
+
+    fn evaluate_unary(&mut self, operation: UnaryOperation, operand: &Node, mode: EvaluatorModifier, host: &mut HostServices) -> Element {
+        if let Some(vc_call) = operand.as_variable() {
+            return self.apply_unary_slot(operation, vc_call.get_slot(), vc_call.get_name());
+        }
+
+        let mut operand = self.evaluate_node(operand, mode - EvaluatorModifier::CopyCall, host);
         if operand.is_error_or_controller() {
             return operand;
         }
 
-        if operand.get_modifier().contains(ElementModifier::Const) {
-            return Element::create_error(Value::Error(ValueError::init_with(
-                String::from("ConstError"),
-                Some(String::from("Cannot perform a `right ++` unary operation to a constant"))
-            )));
-        }
+        Self::apply_unary(operation, &mut operand)
+    }
 
-        if let Value::Pointer(pointer) = operand.get_value() {
-            if let Some(operand_pointer) = self.context.get_element_mut(pointer.get_pointer(), pointer.is_global()) {
-                operand_pointer.increment()
-            } else {
-                Element::create_error(Value::Error(ValueError::init_with(
-                    String::from("AccessError"),
-                    Some(format!("Cannot find the value of a var/const -> {}", pointer.get_pointer())),
-                )))
-            }
+    fn apply_unary_slot(&mut self, operation: UnaryOperation, slot: Option<Slot>, name: &String) -> Element {
+        if let Some(operand) = self.slot_element_mut(slot) {
+            Self::apply_unary(operation, operand)
         } else {
-            operand.increment()
+            Element::create_error(Value::Error(ValueError::init_with(
+                String::from("AccessError"),
+                Some(format!("Cannot find the value of a var/const -> {}", name)),
+            )))
         }
     }
 
-    fn evaluate_decrement(&mut self, decrement: &NodeDecrement, mode: EvaluatorModifier,
-                          logger: &mut LoggerSystem, rights: &mut RightSystem, window: &mut WindowSystem, 
-                          inputs: &mut InputsSystem) -> Element {
-        let mut operand = self.evaluate_node(decrement.get_operand(), mode - EvaluatorModifier::CopyCall, logger, rights, window, inputs);
-        if operand.is_error_or_controller() {
-            return operand;
-        }
+    fn apply_unary(operation: UnaryOperation, operand: &mut Element) -> Element {
+        let symbol = match operation {
+            UnaryOperation::Increment => "++",
+            UnaryOperation::Decrement => "--",
+            UnaryOperation::Not => return operand.not(),
+        };
 
         if operand.get_modifier().contains(ElementModifier::Const) {
             return Element::create_error(Value::Error(ValueError::init_with(
                 String::from("ConstError"),
-                Some(String::from("Cannot perform a `right ++` unary operation to a constant"))
+                Some(format!("Cannot perform a `right {symbol}` unary operation to a constant"))
             )));
         }
 
-        if let Value::Pointer(pointer) = operand.get_value() {
-            if let Some(operand_pointer) = self.context.get_element_mut(pointer.get_pointer(), pointer.is_global()) {
-                operand_pointer.decrement()
-            } else {
-                Element::create_error(Value::Error(ValueError::init_with(
-                    String::from("AccessError"),
-                    Some(format!("Cannot find the value of a var/const -> {}", pointer.get_pointer())),
-                )))
-            }
-        } else {
-            operand.decrement()
-        }
-    }
-
-    fn evaluate_not(&mut self, not: &NodeNot, mode: EvaluatorModifier,
-                    logger: &mut LoggerSystem, rights: &mut RightSystem, window: &mut WindowSystem, 
-                    inputs: &mut InputsSystem) -> Element {
-        let mut operand = self.evaluate_node(not.get_operand(), mode - EvaluatorModifier::CopyCall, logger, rights, window, inputs);
-        if operand.is_error_or_controller() {
-            return operand;
-        }
-
-        if let Value::Pointer(pointer) = operand.get_value() {
-            if let Some(operand_pointer) = self.context.get_element_mut(pointer.get_pointer(), pointer.is_global()) {
-                operand_pointer.not()
-            } else {
-                Element::create_error(Value::Error(ValueError::init_with(
-                    String::from("AccessError"),
-                    Some(format!("Cannot find the value of a var/const -> {}", pointer.get_pointer())),
-                )))
-            }
-        } else {
-            operand.not()
+        match operation {
+            UnaryOperation::Decrement => operand.decrement(),
+            _ => operand.increment(),
         }
     }
     
-    fn evaluate_convert(&mut self, convert: &NodeConvert, mode: EvaluatorModifier, 
-                        logger: &mut LoggerSystem, rights: &mut RightSystem, window: &mut WindowSystem, 
-                        inputs: &mut InputsSystem) -> Element {
-        let left = self.evaluate_node(convert.get_left(), mode | EvaluatorModifier::CopyCall, logger, rights, window, inputs);
+    fn evaluate_convert(&mut self, convert: &NodeConvert, mode: EvaluatorModifier, host: &mut HostServices) -> Element {
+        let left = self.evaluate_node(convert.get_left(), mode | EvaluatorModifier::CopyCall, host);
         if left.is_error_or_controller() {
             return left;
         }
@@ -421,36 +896,32 @@ impl EvaluatorSystem {
         left.convert(convert.get_right())
     }
 
-    fn evaluate_ternary_operation(&mut self, ternary: &NodeTernaryOp, mode: EvaluatorModifier,
-                                  logger: &mut LoggerSystem, rights: &mut RightSystem, window: &mut WindowSystem, 
-                                  inputs: &mut InputsSystem) -> Element {
-        let condition_result = self.evaluate_node(ternary.get_condition(), EvaluatorModifier::CopyCall, logger, rights, window, inputs).check_convert(ApicaTypeBytecode::Bool);
+    fn evaluate_ternary_operation(&mut self, ternary: &NodeTernaryOp, mode: EvaluatorModifier, host: &mut HostServices) -> Element {
+        let condition_result = self.evaluate_node(ternary.get_condition(), EvaluatorModifier::CopyCall, host).check_convert(ApicaTypeBytecode::Bool);
         if condition_result.is_error_or_controller() {
             return condition_result;
         }
 
         if let Value::Bool(result) = condition_result.get_value() {
             if let Some(value) = result.get_value() && value {
-                self.evaluate_node(ternary.get_true_expr(), mode, logger, rights, window, inputs)
+                self.evaluate_node(ternary.get_true_expr(), mode, host)
             } else {
-                self.evaluate_node(ternary.get_false_expr(), mode, logger, rights, window, inputs)
+                self.evaluate_node(ternary.get_false_expr(), mode, host)
             }
         } else {
             condition_result
         }
     }
 
-    fn evaluate_if(&mut self, _if: &NodeIf, mode: EvaluatorModifier,
-                   logger: &mut LoggerSystem, rights: &mut RightSystem, window: &mut WindowSystem, 
-                   inputs: &mut InputsSystem) -> Element {
-        let condition_result = self.evaluate_node(_if.get_condition(), EvaluatorModifier::CopyCall, logger, rights, window, inputs).check_convert(ApicaTypeBytecode::Bool);
+    fn evaluate_if(&mut self, _if: &NodeIf, mode: EvaluatorModifier, host: &mut HostServices) -> Element {
+        let condition_result = self.evaluate_node(_if.get_condition(), EvaluatorModifier::CopyCall, host).check_convert(ApicaTypeBytecode::Bool);
         if condition_result.is_error_or_controller() {
             return condition_result;
         }
 
         if let Value::Bool(result) = condition_result.get_value() {
             if let Some(value) = result.get_value() && value {
-                let body_result = self.evaluate_node(_if.get_body(), mode, logger, rights, window, inputs);
+                let body_result = self.evaluate_node(_if.get_body(), mode, host);
                 if body_result.is_error_or_controller() {
                     return body_result;
                 }
@@ -462,22 +933,20 @@ impl EvaluatorSystem {
         }
     }
 
-    fn evaluate_if_else(&mut self, if_else: &NodeIfElse, mode: EvaluatorModifier,
-                        logger: &mut LoggerSystem, rights: &mut RightSystem, window: &mut WindowSystem, 
-                        inputs: &mut InputsSystem) -> Element {
-        let condition_result = self.evaluate_node(if_else.get_condition(), EvaluatorModifier::CopyCall, logger, rights, window, inputs).check_convert(ApicaTypeBytecode::Bool);
+    fn evaluate_if_else(&mut self, if_else: &NodeIfElse, mode: EvaluatorModifier, host: &mut HostServices) -> Element {
+        let condition_result = self.evaluate_node(if_else.get_condition(), EvaluatorModifier::CopyCall, host).check_convert(ApicaTypeBytecode::Bool);
         if condition_result.is_error_or_controller() {
             return condition_result;
         }
 
         if let Value::Bool(result) = condition_result.get_value() {
             if let Some(value) = result.get_value() && value {
-                let body_result = self.evaluate_node(if_else.get_if_body(), mode, logger, rights, window, inputs);
+                let body_result = self.evaluate_node(if_else.get_if_body(), mode, host);
                 if body_result.is_error_or_controller() {
                     return body_result;
                 }
             } else {
-                let body_result = self.evaluate_node(if_else.get_else_body(), mode, logger, rights, window, inputs);
+                let body_result = self.evaluate_node(if_else.get_else_body(), mode, host);
                 if body_result.is_error_or_controller() {
                     return body_result;
                 }
@@ -489,19 +958,29 @@ impl EvaluatorSystem {
         }
     }
 
-    fn evaluate_while(&mut self, _while: &NodeWhile, mode: EvaluatorModifier,
-                      logger: &mut LoggerSystem, rights: &mut RightSystem, window: &mut WindowSystem, 
-                      inputs: &mut InputsSystem) -> Element {
-        let mut condition_result = self.evaluate_node(_while.get_condition(), EvaluatorModifier::CopyCall, logger, rights, window, inputs).check_convert(ApicaTypeBytecode::Bool);
+    fn evaluate_while(&mut self, _while: &NodeWhile, mode: EvaluatorModifier, host: &mut HostServices) -> Element {
+        let mut condition_result = self.evaluate_node(_while.get_condition(), EvaluatorModifier::CopyCall, host).check_convert(ApicaTypeBytecode::Bool);
         if condition_result.is_error_or_controller() {
             return condition_result;
         }
 
+        let location = self.trace.iter().rev().find_map(|frame| match frame {
+            TraceFrame::Location(location) => Some(location.clone()),
+            _ => None,
+        });
+        self.loops.push(location);
+
         while let Value::Bool(result) = condition_result.get_value() && let Some(value) = result.get_value() && value {
-            self.evaluate_node(_while.get_body(), mode, logger, rights, window, inputs);
-            condition_result = self.evaluate_node(_while.get_condition(), EvaluatorModifier::CopyCall, logger, rights, window, inputs).check_convert(ApicaTypeBytecode::Bool);
+            let body_result = self.evaluate_node(_while.get_body(), mode, host);
+            if body_result.get_modifier().contains(ElementModifier::Error) {
+                self.loops.pop();
+                return body_result;
+            }
+
+            condition_result = self.evaluate_node(_while.get_condition(), EvaluatorModifier::CopyCall, host).check_convert(ApicaTypeBytecode::Bool);
         }
 
+        self.loops.pop();
         if condition_result.is_error_or_controller() {
             return condition_result;
         }
@@ -509,6 +988,42 @@ impl EvaluatorSystem {
         Element::create_null()
     }
 
+    fn evaluate_try_catch(&mut self, try_catch: &NodeTryCatch, mode: EvaluatorModifier, host: &mut HostServices) -> Element {
+        let mut result = self.evaluate_node(try_catch.get_try_body(), mode, host);
+        if result.get_modifier().contains(ElementModifier::Error) {
+            let (error_name, error_details) = Self::error_parts(&result);
+            let (name_slot, details_slot) = try_catch.get_binding_slots();
+            self.bind_error(name_slot, details_slot, error_name, error_details);
+
+            self.error_trace = None;
+            result = self.evaluate_node(try_catch.get_catch_body(), mode, host);
+        }
+
+        if let Some(finally_body) = try_catch.get_finally_body() {
+            let finally_result = self.evaluate_node(finally_body, mode, host);
+            if finally_result.is_error_or_controller() {
+                return finally_result;
+            }
+        }
+
+        if result.is_error_or_controller() {
+            return result;
+        }
+
+        Element::create_null()
+    }
+
+    fn evaluate_located(&mut self, located: &NodeLocated, mode: EvaluatorModifier, host: &mut HostServices) -> Element {
+        self.trace.push(TraceFrame::Location(located.get_location().clone()));
+        let result = self.evaluate_node(located.get_node(), mode, host);
+        if result.get_modifier().contains(ElementModifier::Error) {
+            self.capture_trace();
+        }
+
+        self.trace.pop();
+        result
+    }
+
     fn evaluate_break(&self, _break: &NodeBreak) -> Element {
         Element::init(
             ElementModifier::Controller,
@@ -529,4 +1044,125 @@ impl EvaluatorSystem {
             Value::U8(ValueU8::init_with(0))
         )
     }
+}
+
+fn measure_element(element: &Element) -> u64 {
+    let heap = match element.get_value() {
+        Value::String(value) => value.get_value().as_ref().map_or(0, String::len),
+        Value::Error(value) => value.get_name().as_ref().map_or(0, String::len) + value.get_details().as_ref().map_or(0, String::len),
+        _ => 0,
+    };
+
+    (size_of::<Element>() + heap) as u64
+}
+
+fn measure_slots(slots: &[Option<Element>]) -> u64 {
+    slots.iter().flatten().map(measure_element).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use apica_common::values::u32::ValueU32;
+    use crate::builtins::host::AppServices;
+    use crate::systems::clock::ClockSystem;
+    use crate::systems::compiler::CompilerSystem;
+    use crate::systems::inputs::InputsSystem;
+    use crate::systems::resolver::ResolverSystem;
+    use crate::systems::rights::RightSystem;
+    use crate::systems::storage::DEFAULT_STORAGE_QUOTA;
+    use crate::systems::window::WindowSystem;
+    use crate::utils::format;
+    use super::*;
+
+    fn literal(value: Value) -> Node {
+        Node::Literal(NodeLiteral::init(value))
+    }
+
+    fn number(value: u32) -> Node {
+        literal(Value::U32(ValueU32::init_with(value)))
+    }
+
+    fn text(value: &str) -> Node {
+        literal(Value::String(ValueString::init_with(value.to_string())))
+    }
+
+    fn variable(name: &str) -> Node {
+        Node::VarConstCall(NodeVarConstCall::init(name.to_string()))
+    }
+
+    fn declare(name: &str, kind: ApicaTypeBytecode, expression: Node) -> Node {
+        Node::VarDecl(Box::new(NodeVarDecl::init(name.to_string(), kind, expression)))
+    }
+
+    fn global(statement: Node) -> Node {
+        Node::GlobalScope(Box::new(NodeGlobalScope::init(statement)))
+    }
+
+    fn increment(name: &str) -> Node {
+        Node::Increment(Box::new(NodeIncrement::init(variable(name))))
+    }
+
+    fn decrement(name: &str) -> Node {
+        Node::Decrement(Box::new(NodeDecrement::init(variable(name))))
+    }
+
+    fn program() -> NodeCompound {
+        NodeCompound::init(vec![
+            global(declare("total", ApicaTypeBytecode::U32, Node::Add(Box::new(NodeAdd::init(number(2), number(3)))))),
+            declare("countdown", ApicaTypeBytecode::U32, number(1)),
+            Node::IfElse(Box::new(NodeIfElse::init(
+                variable("countdown"),
+                Node::Compound(NodeCompound::init(vec![increment("total")])),
+                Node::Compound(NodeCompound::init(vec![])),
+            ))),
+            Node::While(Box::new(NodeWhile::init(
+                variable("countdown"),
+                Node::Compound(NodeCompound::init(vec![decrement("countdown"), increment("total")])),
+            ))),
+            global(declare("label", ApicaTypeBytecode::String, Node::Add(Box::new(NodeAdd::init(text("api"), text("ca")))))),
+        ])
+    }
+
+    fn run(mut root: NodeCompound, use_vm: bool, memory_quota: u64) -> Vec<Option<String>> {
+        let mut resolver = ResolverSystem::init();
+        resolver.resolve_entry(&mut root);
+        assert!(resolver.get_errors().is_empty());
+
+        let mut evaluator = EvaluatorSystem::init();
+        evaluator.set_memory_quota(memory_quota);
+        evaluator.prepare_globals(resolver.get_globals().len());
+
+        let (mut logger, mut rights, mut window, mut inputs) = (LoggerSystem::init(false), RightSystem::init(), WindowSystem::init(), InputsSystem::init());
+        let (clock, mut random, mut services) = (ClockSystem::init(), RandomSystem::init(Some(0)), AppServices::init(DEFAULT_STORAGE_QUOTA));
+        let mut host = HostServices::init(&mut logger, &mut rights, &mut window, &mut inputs, &clock, &mut random, &mut services);
+        if use_vm {
+            let program = CompilerSystem::init().compile_entry(&root);
+            evaluator.execute(ApicaEntrypointBytecode::Init, &program, &mut host);
+        } else {
+            evaluator.evaluate(ApicaEntrypointBytecode::Init, &root, &mut host);
+        }
+
+        evaluator.globals.iter().map(|global| global.as_ref().map(format::element_to_string)).collect()
+    }
+
+    #[test]
+    fn both_engines_produce_the_same_globals() {
+        let expected = vec![Some(String::from("7")), Some(String::from("apica"))];
+        assert_eq!(run(program(), false, DEFAULT_MEMORY_QUOTA), expected);
+        assert_eq!(run(program(), true, DEFAULT_MEMORY_QUOTA), expected);
+    }
+
+    #[test]
+    fn exceeding_the_memory_quota_stops_the_entry() {
+        let allocations = || NodeCompound::init(vec![
+            global(declare("first", ApicaTypeBytecode::U32, number(1))),
+            global(declare("second", ApicaTypeBytecode::U32, number(2))),
+            global(declare("third", ApicaTypeBytecode::U32, number(3))),
+        ]);
+
+        let quota = size_of::<Element>() as u64 * 2;
+        let expected = vec![Some(String::from("1")), Some(String::from("2")), None];
+        assert_eq!(run(allocations(), false, quota), expected);
+        assert_eq!(run(allocations(), true, quota), expected);
+    }
 }
\ No newline at end of file
@@ -0,0 +1,205 @@
+﻿use std::collections::HashMap;
+use std::fs::{create_dir_all, read_dir, File, OpenOptions};
+use std::io::{Read, Write};
+use std::path::{Component, Path, PathBuf};
+use apica_common::element::{Element, ElementModifier};
+use apica_common::values::error::ValueError;
+use apica_common::values::string::ValueString;
+use apica_common::values::u32::ValueU32;
+use apica_common::values::value::Value;
+
+#[derive(Copy, Clone, PartialEq)]
+pub enum FileMode {
+    Read,
+    Write,
+    Append,
+}
+
+impl FileMode {
+    pub fn parse(value: &str) -> Option<FileMode> {
+        match value {
+            "r" => Some(FileMode::Read),
+            "w" => Some(FileMode::Write),
+            "a" => Some(FileMode::Append),
+            _ => None,
+        }
+    }
+}
+
+struct OpenFile {
+    file: File,
+    mode: FileMode,
+}
+
+pub struct FileSystem {
+    sandbox: Option<PathBuf>,
+    files: HashMap<u32, OpenFile>,
+    next_handle: u32,
+}
+
+impl FileSystem {
+    pub fn init() -> FileSystem {
+        FileSystem { sandbox: None, files: HashMap::new(), next_handle: 1 }
+    }
+
+    pub fn set_app(&mut self, app_id: &str) {
+        self.sandbox = Some(PathBuf::from(format!("data/{app_id}/files")));
+        self.files.clear();
+        self.next_handle = 1;
+    }
+
+    pub fn open(&mut self, path: &str, mode: FileMode) -> Element {
+        let filepath = match self.resolve(path) {
+            Ok(filepath) => filepath,
+            Err(error) => return error,
+        };
+
+        if mode != FileMode::Read && let Some(parent) = filepath.parent() && let Err(error) = create_dir_all(parent) {
+            return io_error(format!("Cannot create the directory of `{path}` -> {error}"));
+        }
+
+        let result = match mode {
+            FileMode::Read => File::open(&filepath),
+            FileMode::Write => File::create(&filepath),
+            FileMode::Append => OpenOptions::new().append(true).create(true).open(&filepath),
+        };
+
+        match result {
+            Ok(file) => {
+                let handle = self.next_handle;
+                self.next_handle += 1;
+                self.files.insert(handle, OpenFile { file, mode });
+                Element::init(ElementModifier::None, Value::U32(ValueU32::init_with(handle)))
+            },
+            Err(error) => io_error(format!("Cannot open `{path}` -> {error}")),
+        }
+    }
+
+    pub fn read(&mut self, handle: u32) -> Element {
+        let open_file = match self.files.get_mut(&handle) {
+            Some(open_file) if open_file.mode == FileMode::Read => open_file,
+            Some(_) => return io_error(format!("The file handle {handle} was not opened for reading")),
+            None => return invalid_handle_error(handle),
+        };
+
+        let mut content = String::new();
+        match open_file.file.read_to_string(&mut content) {
+            Ok(_) => Element::init(ElementModifier::None, Value::String(ValueString::init_with(content))),
+            Err(error) => io_error(format!("Cannot read the file handle {handle} -> {error}")),
+        }
+    }
+
+    pub fn write(&mut self, handle: u32, content: &str) -> Element {
+        let open_file = match self.files.get_mut(&handle) {
+            Some(open_file) if open_file.mode != FileMode::Read => open_file,
+            Some(_) => return io_error(format!("The file handle {handle} was not opened for writing")),
+            None => return invalid_handle_error(handle),
+        };
+
+        match open_file.file.write_all(content.as_bytes()) {
+            Ok(_) => Element::create_null(),
+            Err(error) => io_error(format!("Cannot write the file handle {handle} -> {error}")),
+        }
+    }
+
+    pub fn close(&mut self, handle: u32) -> Element {
+        match self.files.remove(&handle) {
+            Some(_) => Element::create_null(),
+            None => invalid_handle_error(handle),
+        }
+    }
+
+    pub fn list(&self, path: &str) -> Element {
+        let dirpath = match self.resolve(path) {
+            Ok(dirpath) => dirpath,
+            Err(error) => return error,
+        };
+
+        let entries = match read_dir(&dirpath) {
+            Ok(entries) => entries,
+            Err(error) => return io_error(format!("Cannot list `{path}` -> {error}")),
+        };
+
+        let mut names = vec![];
+        for entry in entries {
+            match entry {
+                Ok(entry) => {
+                    let name = entry.file_name().to_string_lossy().to_string();
+                    names.push(if entry.path().is_dir() { format!("{name}/") } else { name });
+                },
+                Err(error) => return io_error(format!("Cannot list `{path}` -> {error}")),
+            }
+        }
+
+        names.sort();
+        Element::init(ElementModifier::None, Value::String(ValueString::init_with(names.join("\n"))))
+    }
+
+    fn resolve(&self, path: &str) -> Result<PathBuf, Element> {
+        let sandbox = match &self.sandbox {
+            Some(sandbox) => sandbox,
+            None => return Err(io_error(String::from("The app has no file sandbox"))),
+        };
+
+        let relative = Path::new(path);
+        if !relative.components().all(|component| matches!(component, Component::Normal(_) | Component::CurDir)) {
+            return Err(io_error(format!("The path `{path}` leaves the app sandbox")));
+        }
+
+        if let Err(error) = create_dir_all(sandbox) {
+            return Err(io_error(format!("Cannot create the app sandbox -> {error}")));
+        }
+
+        Ok(sandbox.join(relative))
+    }
+}
+
+fn invalid_handle_error(handle: u32) -> Element {
+    io_error(format!("The file handle {handle} is not open"))
+}
+
+fn io_error(details: String) -> Element {
+    Element::create_error(Value::Error(ValueError::init_with(
+        String::from("IOError"),
+        Some(details),
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rejection(files: &FileSystem, path: &str) -> Option<String> {
+        match files.resolve(path) {
+            Ok(_) => None,
+            Err(error) => match error.get_value() {
+                Value::Error(error) => error.get_details().clone(),
+                _ => None,
+            },
+        }
+    }
+
+    #[test]
+    fn paths_leaving_the_sandbox_are_rejected() {
+        let mut files = FileSystem::init();
+        files.set_app("apica_files_test");
+
+        for path in ["../escape.txt", "notes/../../escape.txt", "/etc/passwd", ".."] {
+            let details = rejection(&files, path).unwrap_or_default();
+            assert!(details.contains("leaves the app sandbox"), "`{path}` must be rejected");
+        }
+    }
+
+    #[test]
+    fn an_app_without_sandbox_cannot_resolve_paths() {
+        let files = FileSystem::init();
+        assert_eq!(rejection(&files, "notes.txt").as_deref(), Some("The app has no file sandbox"));
+    }
+
+    #[test]
+    fn unknown_handles_are_reported() {
+        let mut files = FileSystem::init();
+        assert!(files.close(7).is_error_or_controller());
+        assert!(files.write(7, "text").is_error_or_controller());
+    }
+}
\ No newline at end of file
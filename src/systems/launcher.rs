@@ -0,0 +1,98 @@
+﻿use std::path::Path;
+use apica_common::element::{Element, ElementModifier};
+use apica_common::values::error::ValueError;
+use apica_common::values::string::ValueString;
+use apica_common::values::u32::ValueU32;
+use apica_common::values::value::Value;
+use crate::systems::reader;
+use crate::utils::format;
+
+pub struct LaunchRequest {
+    app_name: String,
+    arguments: Vec<Value>,
+}
+
+impl LaunchRequest {
+    pub fn get_app_name(&self) -> &String {
+        &self.app_name
+    }
+}
+
+pub struct LauncherSystem {
+    request: Option<LaunchRequest>,
+    arguments: Vec<Value>,
+    result: Option<Value>,
+    last_result: Option<Value>,
+}
+
+impl LauncherSystem {
+    pub fn init() -> LauncherSystem {
+        LauncherSystem { request: None, arguments: vec![], result: None, last_result: None }
+    }
+
+    pub fn has_request(&self) -> bool {
+        self.request.is_some()
+    }
+
+    pub fn request_launch(&mut self, app_name: &str, arguments: Vec<Element>) -> Element {
+        let valid_name = !app_name.is_empty() && app_name.chars().all(|character| character.is_ascii_alphanumeric() || matches!(character, '_' | '-'));
+        if !valid_name || !Path::new(&reader::get_app_filepath(app_name)).is_file() {
+            return launch_error(format!("Cannot find an app with this name -> {app_name}"));
+        }
+
+        let arguments = arguments.iter().map(|argument| argument.get_value().clone()).collect();
+        self.request = Some(LaunchRequest { app_name: app_name.to_string(), arguments });
+        Element::create_null()
+    }
+
+    pub fn start_app(&mut self) -> Option<LaunchRequest> {
+        let request = self.request.take()?;
+        self.arguments = request.arguments.clone();
+        self.result = None;
+        self.last_result = None;
+        Some(request)
+    }
+
+    pub fn finish_app(&mut self) {
+        self.request = None;
+        self.arguments.clear();
+        self.last_result = self.result.take();
+    }
+
+    pub fn get_argument(&self, index: u32) -> Element {
+        match self.arguments.get(index as usize) {
+            Some(argument) => Element::init(ElementModifier::None, argument.clone()),
+            None => launch_error(format!("There is no launch argument at the index {index}")),
+        }
+    }
+
+    pub fn get_arguments(&self) -> Element {
+        let arguments = self.arguments.iter()
+            .map(|argument| format::element_to_string(&Element::init(ElementModifier::None, argument.clone())))
+            .collect::<Vec<String>>();
+
+        Element::init(ElementModifier::None, Value::String(ValueString::init_with(arguments.join("\n"))))
+    }
+
+    pub fn get_argument_count(&self) -> Element {
+        Element::init(ElementModifier::None, Value::U32(ValueU32::init_with(self.arguments.len() as u32)))
+    }
+
+    pub fn set_result(&mut self, result: &Element) {
+        self.result = Some(result.get_value().clone());
+    }
+
+    pub fn get_result(&self) -> Element {
+        match &self.last_result {
+            Some(result) => Element::init(ElementModifier::None, result.clone()),
+            None => Element::create_null(),
+        }
+    }
+}
+
+fn launch_error(details: String) -> Element {
+    Element::create_error(Value::Error(ValueError::init_with(
+        String::from("LaunchError"),
+        Some(details),
+    )))
+}
\ No newline at end of file
@@ -1,13 +1,27 @@
-﻿use std::fs::{create_dir_all, File};
+﻿use std::fs::{create_dir_all, File, OpenOptions};
 use std::io::Write;
-use apica_common::bytecodes::ApicaTypeBytecode;
 use apica_common::element::Element;
-use apica_common::values::value::Value;
 use chrono::Local;
+use crate::utils::config::{LogFormat, LogLevel};
+use crate::utils::format;
+use crate::utils::logging::{self, AnsiFormatter, LogFormatter, LogRecord};
+
+pub const LOG_TIMESTAMP_FORMAT: &str = "%Y-%m-%dT%H:%M:%S%.3f%:z";
 
 pub struct LoggerSystem {
-    actual_file: Option<File>,
-    actual_date: Option<String>
+    app_name: Option<String>,
+    app_id: Option<String>,
+    app_file: Option<File>,
+    app_active: bool,
+    system_file: Option<File>,
+    actual_date: Option<String>,
+    min_level: LogLevel,
+    system_min_level: LogLevel,
+    formatter: Box<dyn LogFormatter>,
+    console: bool,
+    frame: u64,
+    // `log_*` calls without a newline accumulate here until a `logn_*` call completes the record.
+    pending: Option<(LogLevel, String)>,
 }
 
 impl LoggerSystem {
@@ -22,78 +36,193 @@ impl LoggerSystem {
             let _ = create_dir_all(dirpath).is_err();
         }
 
-        LoggerSystem { actual_file: None, actual_date: date }
+        let mut logger = LoggerSystem {
+            app_name: None,
+            app_id: None,
+            app_file: None,
+            app_active: true,
+            system_file: None,
+            actual_date: date,
+            min_level: LogLevel::Info,
+            system_min_level: LogLevel::Info,
+            formatter: logging::create_formatter(LogFormat::Plain),
+            console: false,
+            frame: 0,
+            pending: None,
+        };
+
+        logger.open_system_file();
+        logger
+    }
+
+    pub fn set_format(&mut self, format: LogFormat) {
+        self.formatter = logging::create_formatter(format);
+        self.open_system_file();
+    }
+
+    pub fn set_console(&mut self, console: bool) {
+        self.console = console;
+    }
+
+    pub fn set_min_level(&mut self, min_level: LogLevel) {
+        self.min_level = min_level;
+    }
+
+    pub fn set_system_min_level(&mut self, system_min_level: LogLevel) {
+        self.system_min_level = system_min_level;
+    }
+
+    pub fn set_app(&mut self, app_name: &str) {
+        self.flush_pending();
+        self.app_name = Some(app_name.to_string());
+        self.app_id = None;
+        self.app_file = None;
+        self.app_active = true;
+        self.frame = 0;
+    }
+
+    pub fn set_app_id(&mut self, app_id: Option<&str>) {
+        self.app_id = app_id.map(|app_id| app_id.to_string());
     }
 
-    pub fn create_file_for(&mut self, app_name: &str) {
+    pub fn set_app_activation(&mut self, active: bool) {
+        self.app_active = active;
+    }
+
+    pub fn set_frame(&mut self, frame: u64) {
+        self.frame = frame;
+    }
+
+    pub fn system_write_file(&self, filename: &str, content: &str) {
         if let Some(actual_date) = &self.actual_date {
-            let filepath = format!("logs/{}/{}.log", actual_date, app_name);
-            self.actual_file = if let Ok(file) = File::create(filepath) { Some(file) } else { None };
+            let filepath = format!("logs/{}/{}", actual_date, filename);
+            if let Ok(mut file) = File::create(filepath) {
+                let _ = file.write_all(content.as_bytes());
+            }
         }
     }
 
     pub fn system_logn_success(&mut self, message: String) {
-        if let Some(file) = &mut self.actual_file {
-            let _ = writeln!(file, "\x1b[32mAPC_SUC: {message}\x1b[0m");
-        }
+        self.system_logn(LogLevel::Success, &message);
+    }
+
+    pub fn system_logn_warning(&mut self, message: String) {
+        self.system_logn(LogLevel::Warning, &message);
     }
 
     pub fn system_logn_error(&mut self, message: String) {
-        if let Some(file) = &mut self.actual_file {
-            let _ = writeln!(file, "\x1b[31mAPC_ERR: {message}\x1b[0m");
-        }
+        self.system_logn(LogLevel::Error, &message);
     }
 
     pub fn log_info(&mut self, parameters: Vec<Element>) {
-        self.log_parameters(parameters, "\x1b[37mINF: ", "\x1b[0m");
+        self.log_parameters(LogLevel::Info, parameters, false);
     }
 
     pub fn logn_info(&mut self, parameters: Vec<Element>) {
-        self.log_parameters(parameters, "\x1b[37mINF: ", "\x1b[0m\n");
+        self.log_parameters(LogLevel::Info, parameters, true);
     }
 
     pub fn log_success(&mut self, parameters: Vec<Element>) {
-        self.log_parameters(parameters, "\x1b[32mSUC: ", "\x1b[0m");
+        self.log_parameters(LogLevel::Success, parameters, false);
     }
 
     pub fn logn_success(&mut self, parameters: Vec<Element>) {
-        self.log_parameters(parameters, "\x1b[32mSUC: ", "\x1b[0m\n");
+        self.log_parameters(LogLevel::Success, parameters, true);
     }
 
     pub fn log_warning(&mut self, parameters: Vec<Element>) {
-        self.log_parameters(parameters, "\x1b[33mWRN: ", "\x1b[0m");
+        self.log_parameters(LogLevel::Warning, parameters, false);
     }
 
     pub fn logn_warning(&mut self, parameters: Vec<Element>) {
-        self.log_parameters(parameters, "\x1b[33mWRN: ", "\x1b[0m\n");
+        self.log_parameters(LogLevel::Warning, parameters, true);
     }
 
     pub fn log_error(&mut self, parameters: Vec<Element>) {
-        self.log_parameters(parameters, "\x1b[31mERR: ", "\x1b[0m");
+        self.log_parameters(LogLevel::Error, parameters, false);
     }
 
     pub fn logn_error(&mut self, parameters: Vec<Element>) {
-        self.log_parameters(parameters, "\x1b[31mERR: ", "\x1b[0m\n");
-    }
-
-    fn log_parameters(&mut self, parameters: Vec<Element>, start: &str, end: &str) {
-        if let Some(file) = &mut self.actual_file {
-            let _ = write!(file, "{start}");
-            for param in &parameters {
-                if let Value::String(parameter) = param.get_value() {
-                    let _ = write!(file, "{}", parameter.get_value().as_ref().unwrap_or(&"null".to_string()));
-                } else {
-                    let converted = param.convert(ApicaTypeBytecode::String);
-                    let parameter = match converted.get_value() {
-                        Value::String(s) => s,
-                        _ => unreachable!(),
-                    };
-
-                    let _ = write!(file, "{}", parameter.get_value().as_ref().unwrap_or(&"null".to_string()));
-                }
+        self.log_parameters(LogLevel::Error, parameters, true);
+    }
+
+    fn open_system_file(&mut self) {
+        self.system_file = self.actual_date.as_ref().and_then(|actual_date| {
+            let filepath = format!("logs/{actual_date}/system.{}", self.formatter.get_extension());
+            OpenOptions::new().append(true).create(true).open(filepath).ok()
+        });
+    }
+
+    fn get_app_label(&self) -> &str {
+        self.app_id.as_deref().or(self.app_name.as_deref()).unwrap_or("-")
+    }
+
+    fn system_logn(&mut self, level: LogLevel, message: &str) {
+        if level < self.system_min_level {
+            return;
+        }
+
+        let record = LogRecord::init(Local::now().format(LOG_TIMESTAMP_FORMAT).to_string(), level, true, self.get_app_label(), self.frame, message);
+        let line = self.formatter.format(&record);
+        if self.console {
+            println!("{}", AnsiFormatter.format(&record));
+        }
+
+        if let Some(file) = &mut self.system_file {
+            let _ = writeln!(file, "{line}");
+        }
+    }
+
+    fn get_app_file(&mut self) -> Option<&mut File> {
+        if !self.app_active {
+            return None;
+        }
+
+        if self.app_file.is_none() && let Some(actual_date) = &self.actual_date && let Some(app_name) = &self.app_name {
+            self.app_file = File::create(format!("logs/{actual_date}/{app_name}.{}", self.formatter.get_extension())).ok();
+        }
+
+        self.app_file.as_mut()
+    }
+
+    fn log_parameters(&mut self, level: LogLevel, parameters: Vec<Element>, newline: bool) {
+        if level >= self.min_level {
+            let text = parameters.iter().map(format::element_to_string).collect::<String>();
+            match &mut self.pending {
+                Some((pending_level, pending_text)) => {
+                    if level > *pending_level {
+                        *pending_level = level;
+                    }
+
+                    pending_text.push_str(&text);
+                },
+                None => self.pending = Some((level, text)),
             }
+        }
+
+        if newline {
+            self.flush_pending();
+        }
+    }
+
+    fn flush_pending(&mut self) {
+        let (level, message) = match self.pending.take() {
+            Some(pending) => pending,
+            None => return,
+        };
+
+        if !self.app_active {
+            return;
+        }
+
+        let record = LogRecord::init(Local::now().format(LOG_TIMESTAMP_FORMAT).to_string(), level, false, self.get_app_label(), self.frame, &message);
+        let line = self.formatter.format(&record);
+        if self.console {
+            println!("{}", AnsiFormatter.format(&record));
+        }
 
-            let _ = write!(file, "{end}");
+        if let Some(file) = self.get_app_file() {
+            let _ = writeln!(file, "{line}");
         }
     }
 }
\ No newline at end of file
@@ -4,4 +4,17 @@ pub mod apica;
 pub mod evaluator;
 pub mod rights;
 pub mod inputs;
-pub mod window;
\ No newline at end of file
+pub mod window;
+pub mod random;
+pub mod clock;
+pub mod optimizer;
+pub mod compiler;
+pub mod resolver;
+pub mod checker;
+pub mod plugins;
+pub mod state;
+pub mod storage;
+pub mod files;
+pub mod launcher;
+pub mod catalog;
+pub mod mods;
\ No newline at end of file
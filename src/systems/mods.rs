@@ -0,0 +1,121 @@
+﻿use std::collections::HashMap;
+use std::fs::read_dir;
+use apica_common::element::Element;
+use apica_common::values::value::Value;
+use crate::systems::compiler::Program;
+use crate::systems::logger::LoggerSystem;
+use crate::systems::reader::BytecodeReaderSystem;
+use crate::utils::config::SystemConfig;
+use crate::utils::rights::ApicaCapability;
+
+pub const APICA_MODS_DIRECTORY: &str = "mods";
+pub const APICA_MODS_CONFIG_FILE: &str = "mods.cfg";
+const APP_ONLY_SPECIFICATIONS: [&str; 3] = ["title", "window_width", "window_height"];
+
+pub struct LoadedMod {
+    name: String,
+    reader: BytecodeReaderSystem,
+    programs: HashMap<u64, Program>,
+    globals: Vec<Option<Element>>,
+    capabilities: ApicaCapability,
+}
+
+impl LoadedMod {
+    pub fn init(name: &str, reader: BytecodeReaderSystem, programs: HashMap<u64, Program>,
+                global_count: usize, capabilities: ApicaCapability) -> LoadedMod {
+        let mut globals = vec![];
+        globals.resize_with(global_count, || None);
+        LoadedMod { name: name.to_string(), reader, programs, globals, capabilities }
+    }
+
+    pub fn get_name(&self) -> &String {
+        &self.name
+    }
+
+    pub fn get_capabilities(&self) -> ApicaCapability {
+        self.capabilities
+    }
+
+    pub fn get_id(&self) -> Option<&String> {
+        match self.reader.get_data("id") {
+            Some(Value::String(id)) => id.get_value().as_ref(),
+            _ => None,
+        }
+    }
+
+    pub fn swap_code(&mut self, reader: &mut BytecodeReaderSystem, programs: &mut HashMap<u64, Program>) {
+        std::mem::swap(&mut self.reader, reader);
+        std::mem::swap(&mut self.programs, programs);
+    }
+
+    pub fn get_globals_mut(&mut self) -> &mut Vec<Option<Element>> {
+        &mut self.globals
+    }
+}
+
+pub struct ModSystem {
+    mods: Vec<LoadedMod>,
+}
+
+impl ModSystem {
+    pub fn init() -> ModSystem {
+        ModSystem { mods: vec![] }
+    }
+
+    pub fn clear(&mut self) {
+        self.mods.clear();
+    }
+
+    pub fn get_mods_mut(&mut self) -> &mut Vec<LoadedMod> {
+        &mut self.mods
+    }
+
+    pub fn get_count(&self) -> usize {
+        self.mods.len()
+    }
+
+    pub fn find_mods(app_id: &str, logger: &mut LoggerSystem) -> Vec<(String, String)> {
+        let directory = format!("{APICA_MODS_DIRECTORY}/{app_id}");
+        let config = SystemConfig::load(&format!("{directory}/{APICA_MODS_CONFIG_FILE}"));
+
+        let mut mods = vec![];
+        if let Ok(entries) = read_dir(&directory) {
+            for entry in entries.flatten() {
+                let path = entry.path();
+                if path.extension().is_none_or(|extension| extension != "apb") || !path.is_file() {
+                    continue;
+                }
+
+                let name = match path.file_stem() {
+                    Some(name) => name.to_string_lossy().to_string(),
+                    None => continue,
+                };
+
+                if config.get_bool(&name).unwrap_or(true) {
+                    mods.push((name, path.to_string_lossy().to_string()));
+                } else {
+                    logger.system_logn_success(format!("Mod `{name}` is disabled for `{app_id}`"));
+                }
+            }
+        }
+
+        mods.sort();
+        mods
+    }
+
+    pub fn add(&mut self, loaded_mod: LoadedMod, logger: &mut LoggerSystem) {
+        for specification in APP_ONLY_SPECIFICATIONS {
+            if loaded_mod.reader.get_data(specification).is_some() {
+                logger.system_logn_error(format!("Mod `{}` sets `{specification}`, which only the app can set; it is ignored", loaded_mod.get_name()));
+            }
+        }
+
+        if let Some(id) = loaded_mod.get_id() && let Some(other) = self.mods.iter().find(|other| other.get_id() == Some(id)) {
+            logger.system_logn_error(format!("Mod `{}` conflicts with mod `{}`: both declare the id `{id}`, so `{}` was skipped",
+                                             loaded_mod.get_name(), other.get_name(), loaded_mod.get_name()));
+            return;
+        }
+
+        self.mods.push(loaded_mod);
+    }
+}
\ No newline at end of file
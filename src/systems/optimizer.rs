@@ -0,0 +1,348 @@
+﻿use apica_common::bytecodes::ApicaTypeBytecode;
+use apica_common::element::{Element, ElementModifier};
+use apica_common::values::value::Value;
+use crate::nodes::_if::NodeIf;
+use crate::nodes::_while::NodeWhile;
+use crate::nodes::add::NodeAdd;
+use crate::nodes::builtin_func_call::NodeBuiltinFuncCall;
+use crate::nodes::compound::NodeCompound;
+use crate::nodes::const_decl::NodeConstDecl;
+use crate::nodes::convert::NodeConvert;
+use crate::nodes::decrement::NodeDecrement;
+use crate::nodes::global_scope::NodeGlobalScope;
+use crate::nodes::if_else::NodeIfElse;
+use crate::nodes::increment::NodeIncrement;
+use crate::nodes::literal::NodeLiteral;
+use crate::nodes::located::NodeLocated;
+use crate::nodes::named_func_call::NodeNamedFuncCall;
+use crate::nodes::node::Node;
+use crate::nodes::not::NodeNot;
+use crate::nodes::ternary_op::NodeTernaryOp;
+use crate::nodes::try_catch::NodeTryCatch;
+use crate::nodes::var_decl::NodeVarDecl;
+
+pub struct OptimizerSystem {
+    folded: u32,
+    removed: u32,
+    flattened: u32,
+}
+
+impl OptimizerSystem {
+    pub fn init() -> OptimizerSystem {
+        OptimizerSystem { folded: 0, removed: 0, flattened: 0 }
+    }
+
+    pub fn get_summary(&self) -> String {
+        format!("{} folded expressions, {} removed branches, {} flattened compounds", self.folded, self.removed, self.flattened)
+    }
+
+    pub fn optimize_entry(&mut self, entry: NodeCompound) -> NodeCompound {
+        NodeCompound::init(self.optimize_statements(entry.into_nodes()))
+    }
+
+    fn optimize_statements(&mut self, nodes: Vec<Node>) -> Vec<Node> {
+        let mut result = vec![];
+        for node in nodes {
+            match self.optimize_node(node) {
+                Some(Node::Compound(compound)) if !Self::declares_locals(&compound) => {
+                    self.flattened += 1;
+                    result.extend(compound.into_nodes());
+                },
+
+                Some(node) => result.push(node),
+                None => {},
+            }
+        }
+
+        result
+    }
+
+    fn declares_locals(compound: &NodeCompound) -> bool {
+        compound.get_nodes().iter().any(|node| {
+            let mut node = node;
+            while let Node::Located(located) = node {
+                node = located.get_node();
+            }
+
+            match node {
+                Node::VarDecl(_) | Node::ConstDecl(_) => true,
+                Node::Compound(nested) => Self::declares_locals(nested),
+                _ => false,
+            }
+        })
+    }
+
+    fn literal_of(node: &Node) -> Option<&Value> {
+        if let Node::Literal(literal) = node {
+            Some(literal.get_value())
+        } else {
+            None
+        }
+    }
+
+    fn literal_condition(node: &Node) -> Option<bool> {
+        let value = Self::literal_of(node)?;
+        let condition = Element::init(ElementModifier::None, value.clone()).check_convert(ApicaTypeBytecode::Bool);
+        if condition.is_error_or_controller() {
+            return None;
+        }
+
+        if let Value::Bool(result) = condition.get_value() {
+            Some(result.get_value().unwrap_or(false))
+        } else {
+            None
+        }
+    }
+
+    fn fold(&mut self, element: Element) -> Option<Node> {
+        if element.is_error_or_controller() {
+            return None;
+        }
+
+        self.folded += 1;
+        Some(Node::Literal(NodeLiteral::init(element.get_value().clone())))
+    }
+
+    fn optimize_required(&mut self, node: Node) -> Node {
+        self.optimize_node(node).unwrap_or(Node::Compound(NodeCompound::init(vec![])))
+    }
+
+    fn optimize_node(&mut self, node: Node) -> Option<Node> {
+        match node {
+            Node::Compound(compound) => Some(Node::Compound(NodeCompound::init(self.optimize_statements(compound.into_nodes())))),
+
+            Node::BuiltinFuncCall(builtin) => {
+                let (function, parameters) = builtin.into_parts();
+                let parameters = parameters.into_iter().map(|parameter| self.optimize_required(parameter)).collect();
+                Some(Node::BuiltinFuncCall(NodeBuiltinFuncCall::init(function, parameters)))
+            },
+
+            Node::NamedFuncCall(named) => {
+                let (name, parameters) = named.into_parts();
+                let parameters = parameters.into_iter().map(|parameter| self.optimize_required(parameter)).collect();
+                Some(Node::NamedFuncCall(NodeNamedFuncCall::init(name, parameters)))
+            },
+
+            Node::GlobalScope(global_scope) => {
+                let statement = self.optimize_required(global_scope.into_statement());
+                Some(Node::GlobalScope(Box::new(NodeGlobalScope::init(statement))))
+            },
+
+            Node::VarDecl(var_decl) => {
+                let (name, value_kind, expression) = var_decl.into_parts();
+                let expression = self.optimize_required(expression);
+                Some(Node::VarDecl(Box::new(NodeVarDecl::init(name, value_kind, expression))))
+            },
+
+            Node::ConstDecl(const_decl) => {
+                let (name, value_kind, expression) = const_decl.into_parts();
+                let expression = self.optimize_required(expression);
+                Some(Node::ConstDecl(Box::new(NodeConstDecl::init(name, value_kind, expression))))
+            },
+
+            Node::Add(add) => {
+                let (left, right) = add.into_parts();
+                let (left, right) = (self.optimize_required(left), self.optimize_required(right));
+                if let (Some(left_value), Some(right_value)) = (Self::literal_of(&left), Self::literal_of(&right)) {
+                    let result = Element::init(ElementModifier::None, left_value.clone())
+                        .add(&Element::init(ElementModifier::None, right_value.clone()));
+                    if let Some(folded) = self.fold(result) {
+                        return Some(folded);
+                    }
+                }
+
+                Some(Node::Add(Box::new(NodeAdd::init(left, right))))
+            },
+
+            Node::Increment(increment) => {
+                let operand = self.optimize_required(increment.into_operand());
+                Some(Node::Increment(Box::new(NodeIncrement::init(operand))))
+            },
+
+            Node::Decrement(decrement) => {
+                let operand = self.optimize_required(decrement.into_operand());
+                Some(Node::Decrement(Box::new(NodeDecrement::init(operand))))
+            },
+
+            Node::Not(not) => {
+                let operand = self.optimize_required(not.into_operand());
+                if let Some(value) = Self::literal_of(&operand) {
+                    let result = Element::init(ElementModifier::None, value.clone()).not();
+                    if let Some(folded) = self.fold(result) {
+                        return Some(folded);
+                    }
+                }
+
+                Some(Node::Not(Box::new(NodeNot::init(operand))))
+            },
+
+            Node::Convert(convert) => {
+                let (left, right) = convert.into_parts();
+                let left = self.optimize_required(left);
+                if let Some(value) = Self::literal_of(&left) {
+                    let result = Element::init(ElementModifier::None, value.clone()).convert(right);
+                    if let Some(folded) = self.fold(result) {
+                        return Some(folded);
+                    }
+                }
+
+                Some(Node::Convert(Box::new(NodeConvert::init(left, right))))
+            },
+
+            Node::TernaryOp(ternary) => {
+                let (condition, true_expr, false_expr) = ternary.into_parts();
+                let condition = self.optimize_required(condition);
+                match Self::literal_condition(&condition) {
+                    Some(true) => {
+                        self.removed += 1;
+                        Some(self.optimize_required(true_expr))
+                    },
+
+                    Some(false) => {
+                        self.removed += 1;
+                        Some(self.optimize_required(false_expr))
+                    },
+
+                    None => {
+                        let (true_expr, false_expr) = (self.optimize_required(true_expr), self.optimize_required(false_expr));
+                        Some(Node::TernaryOp(Box::new(NodeTernaryOp::init(condition, true_expr, false_expr))))
+                    },
+                }
+            },
+
+            Node::If(_if) => {
+                let (condition, body) = _if.into_parts();
+                let condition = self.optimize_required(condition);
+                match Self::literal_condition(&condition) {
+                    Some(true) => {
+                        self.removed += 1;
+                        self.optimize_node(body)
+                    },
+
+                    Some(false) => {
+                        self.removed += 1;
+                        None
+                    },
+
+                    None => {
+                        let body = self.optimize_required(body);
+                        Some(Node::If(Box::new(NodeIf::init(condition, body))))
+                    },
+                }
+            },
+
+            Node::IfElse(if_else) => {
+                let (condition, if_body, else_body) = if_else.into_parts();
+                let condition = self.optimize_required(condition);
+                match Self::literal_condition(&condition) {
+                    Some(true) => {
+                        self.removed += 1;
+                        self.optimize_node(if_body)
+                    },
+
+                    Some(false) => {
+                        self.removed += 1;
+                        self.optimize_node(else_body)
+                    },
+
+                    None => {
+                        let (if_body, else_body) = (self.optimize_required(if_body), self.optimize_required(else_body));
+                        Some(Node::IfElse(Box::new(NodeIfElse::init(condition, if_body, else_body))))
+                    },
+                }
+            },
+
+            Node::While(_while) => {
+                let (condition, body) = _while.into_parts();
+                let condition = self.optimize_required(condition);
+                if Self::literal_condition(&condition) == Some(false) {
+                    self.removed += 1;
+                    return None;
+                }
+
+                let body = self.optimize_required(body);
+                Some(Node::While(Box::new(NodeWhile::init(condition, body))))
+            },
+
+            Node::TryCatch(try_catch) => {
+                let (try_body, error_name, error_details, catch_body, finally_body) = try_catch.into_parts();
+                let try_body = self.optimize_required(try_body);
+                let catch_body = self.optimize_required(catch_body);
+                let finally_body = finally_body.map(|finally_body| self.optimize_required(finally_body));
+                Some(Node::TryCatch(Box::new(NodeTryCatch::init(try_body, error_name, error_details, catch_body, finally_body))))
+            },
+
+            Node::Located(located) => {
+                let (location, node) = located.into_parts();
+                match self.optimize_node(node)? {
+                    Node::Literal(literal) => Some(Node::Literal(literal)),
+                    node => Some(Node::Located(Box::new(NodeLocated::init(location, node)))),
+                }
+            },
+
+            node => Some(node),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use apica_common::values::bool::ValueBool;
+    use apica_common::values::u32::ValueU32;
+    use crate::utils::format;
+    use super::*;
+
+    fn number(value: u32) -> Node {
+        Node::Literal(NodeLiteral::init(Value::U32(ValueU32::init_with(value))))
+    }
+
+    fn condition(value: bool) -> Node {
+        Node::Literal(NodeLiteral::init(Value::Bool(ValueBool::init_with(value))))
+    }
+
+    fn addition(left: Node, right: Node) -> Node {
+        Node::Add(Box::new(NodeAdd::init(left, right)))
+    }
+
+    #[test]
+    fn literal_additions_are_folded() {
+        let mut optimizer = OptimizerSystem::init();
+        let entry = optimizer.optimize_entry(NodeCompound::init(vec![addition(addition(number(2), number(3)), number(4))]));
+
+        match entry.get_nodes().as_slice() {
+            [Node::Literal(literal)] => {
+                let element = Element::init(ElementModifier::None, literal.get_value().clone());
+                assert_eq!(format::element_to_string(&element), "9");
+            },
+            _ => panic!("the addition must be folded into a single literal"),
+        }
+
+        assert!(optimizer.get_summary().starts_with("2 folded expressions"));
+    }
+
+    #[test]
+    fn constant_branches_are_removed() {
+        let mut optimizer = OptimizerSystem::init();
+        let entry = optimizer.optimize_entry(NodeCompound::init(vec![
+            Node::If(Box::new(NodeIf::init(condition(false), number(1)))),
+            Node::If(Box::new(NodeIf::init(condition(true), number(2)))),
+        ]));
+
+        assert_eq!(entry.get_nodes().len(), 1);
+        assert!(matches!(entry.get_nodes()[0], Node::Literal(_)));
+        assert!(optimizer.get_summary().contains("2 removed branches"));
+    }
+
+    #[test]
+    fn nested_declarations_are_not_hoisted() {
+        let declaration = Node::VarDecl(Box::new(NodeVarDecl::init(String::from("hidden"), ApicaTypeBytecode::U32, number(1))));
+        let body = Node::Compound(NodeCompound::init(vec![Node::Compound(NodeCompound::init(vec![declaration]))]));
+
+        let mut optimizer = OptimizerSystem::init();
+        let entry = optimizer.optimize_entry(NodeCompound::init(vec![Node::If(Box::new(NodeIf::init(condition(true), body)))]));
+
+        assert_eq!(entry.get_nodes().len(), 1);
+        assert!(matches!(entry.get_nodes()[0], Node::Compound(_)));
+        assert!(optimizer.get_summary().contains("0 flattened compounds"));
+    }
+}
\ No newline at end of file
@@ -0,0 +1,70 @@
+﻿use crate::builtins::registry::BuiltinRegistry;
+use crate::systems::logger::LoggerSystem;
+#[cfg(any(feature = "linked-plugins", feature = "dynamic-plugins"))]
+use crate::plugins::plugin::PluginRegistrar;
+
+pub struct PluginSystem {
+    #[cfg(feature = "dynamic-plugins")]
+    libraries: Vec<libloading::Library>,
+}
+
+impl PluginSystem {
+    pub fn init() -> PluginSystem {
+        PluginSystem {
+            #[cfg(feature = "dynamic-plugins")]
+            libraries: vec![],
+        }
+    }
+
+    #[cfg_attr(not(any(feature = "linked-plugins", feature = "dynamic-plugins")), expect(unused_variables))]
+    pub fn load_plugins(&mut self, registry: &mut BuiltinRegistry, logger: &mut LoggerSystem) {
+        #[cfg(feature = "linked-plugins")]
+        for plugin in crate::plugins::linked::linked_plugins() {
+            let mut registrar = PluginRegistrar::init(registry);
+            plugin.register(&mut registrar);
+            Self::report(plugin.get_name(), &registrar, logger);
+        }
+
+        #[cfg(feature = "dynamic-plugins")]
+        self.load_dynamic_plugins(registry, logger);
+    }
+
+    #[cfg(any(feature = "linked-plugins", feature = "dynamic-plugins"))]
+    fn report(plugin_name: &str, registrar: &PluginRegistrar, logger: &mut LoggerSystem) {
+        for conflict in registrar.get_conflicts() {
+            logger.system_logn_error(format!("The plugin `{plugin_name}` tried to register an existing builtin -> {conflict}"));
+        }
+
+        logger.system_logn_success(format!("Plugin `{plugin_name}` was loaded with {} builtin(s)", registrar.get_registered().len()));
+    }
+
+    #[cfg(feature = "dynamic-plugins")]
+    fn load_dynamic_plugins(&mut self, registry: &mut BuiltinRegistry, logger: &mut LoggerSystem) {
+        use crate::plugins::dynamic::{self, APICA_PLUGIN_DIRECTORY};
+
+        let entries = match std::fs::read_dir(APICA_PLUGIN_DIRECTORY) {
+            Ok(entries) => entries,
+            Err(_) => return,
+        };
+
+        let mut paths: Vec<_> = entries.flatten().map(|entry| entry.path())
+            .filter(|path| path.extension().is_some_and(|extension| extension == std::env::consts::DLL_EXTENSION))
+            .collect();
+        paths.sort();
+
+        for path in paths {
+            match dynamic::load_plugin(&path) {
+                Ok((library, name, handlers)) => {
+                    let mut registrar = PluginRegistrar::init(registry);
+                    for handler in handlers {
+                        registrar.add_builtin(handler);
+                    }
+
+                    Self::report(&name, &registrar, logger);
+                    self.libraries.push(library);
+                },
+                Err(details) => logger.system_logn_error(format!("Failed to load the plugin `{}`: {details}", path.display())),
+            }
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,107 @@
+﻿use std::time::{SystemTime, UNIX_EPOCH};
+use apica_common::element::Element;
+use crate::utils::math::Number;
+
+pub struct RandomSystem {
+    state: u64,
+    fixed_seed: Option<u64>,
+}
+
+impl RandomSystem {
+    pub fn init(fixed_seed: Option<u64>) -> RandomSystem {
+        let mut random = RandomSystem { state: 0, fixed_seed };
+        random.reset();
+        random
+    }
+
+    pub fn reset(&mut self) {
+        let seed = self.fixed_seed.unwrap_or_else(|| {
+            SystemTime::now().duration_since(UNIX_EPOCH).map(|duration| duration.as_nanos() as u64).unwrap_or(0)
+        });
+
+        self.system_seed(seed);
+    }
+
+    pub fn system_seed(&mut self, seed: u64) {
+        let mut mixed = seed.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        mixed = (mixed ^ (mixed >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        mixed = (mixed ^ (mixed >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        mixed ^= mixed >> 31;
+
+        self.state = if mixed == 0 { 0x9E37_79B9_7F4A_7C15 } else { mixed };
+    }
+
+    pub fn system_next_u64(&mut self) -> u64 {
+        self.state ^= self.state >> 12;
+        self.state ^= self.state << 25;
+        self.state ^= self.state >> 27;
+        self.state.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+
+    pub fn system_next_f64(&mut self) -> f64 {
+        (self.system_next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    pub fn system_next_range(&mut self, min: i64, max: i64) -> i64 {
+        let span = (max as i128 - min as i128 + 1) as u128;
+        let offset = (self.system_next_u64() as u128 * span) >> 64;
+        (min as i128 + offset as i128) as i64
+    }
+
+    pub fn random_seed(&mut self, parameters: Vec<Element>) -> bool {
+        if parameters.len() != 1 {
+            return false;
+        }
+
+        if let Some(Number::Integer(seed)) = Number::from_element(parameters.first().unwrap()) {
+            if self.fixed_seed.is_none() {
+                self.system_seed(seed as u64);
+            }
+
+            return true;
+        }
+
+        false
+    }
+
+    pub fn random_int(&mut self, parameters: Vec<Element>) -> Option<i64> {
+        if parameters.len() != 2 {
+            return None;
+        }
+
+        if let Some(Number::Integer(min)) = Number::from_element(&parameters[0])
+            && let Some(Number::Integer(max)) = Number::from_element(&parameters[1])
+            && min <= max {
+            return Some(self.system_next_range(min, max));
+        }
+
+        None
+    }
+
+    pub fn random_float(&mut self, parameters: Vec<Element>) -> Option<f64> {
+        match parameters.len() {
+            0 => Some(self.system_next_f64()),
+
+            2 => {
+                let min = Number::from_element(&parameters[0])?.as_f64();
+                let max = Number::from_element(&parameters[1])?.as_f64();
+                if min > max {
+                    return None;
+                }
+
+                Some(min + (max - min) * self.system_next_f64())
+            },
+
+            _ => None,
+        }
+    }
+
+    pub fn random_choice(&mut self, mut parameters: Vec<Element>) -> Option<Element> {
+        if parameters.is_empty() {
+            return None;
+        }
+
+        let index = self.system_next_range(0, parameters.len() as i64 - 1) as usize;
+        Some(parameters.swap_remove(index))
+    }
+}
\ No newline at end of file
@@ -1,5 +1,6 @@
 ﻿use std::collections::HashMap;
 use std::fs::File;
+use std::rc::Rc;
 use apica_common::bytecodes::{ApicaBytecode, ApicaEntrypointBytecode, ApicaSpecificationBytecode, ApicaTypeBytecode};
 use apica_common::values::bool::ValueBool;
 use apica_common::values::null::ValueNull;
@@ -22,57 +23,148 @@ use crate::nodes::global_scope::NodeGlobalScope;
 use crate::nodes::if_else::NodeIfElse;
 use crate::nodes::increment::NodeIncrement;
 use crate::nodes::literal::NodeLiteral;
+use crate::nodes::located::{NodeLocated, SourceLocation};
+use crate::nodes::named_func_call::NodeNamedFuncCall;
 use crate::nodes::node::Node;
 use crate::nodes::not::NodeNot;
 use crate::nodes::ternary_op::NodeTernaryOp;
+use crate::nodes::try_catch::NodeTryCatch;
 use crate::nodes::var_const_call::NodeVarConstCall;
 use crate::nodes::var_decl::NodeVarDecl;
 use crate::systems::logger::LoggerSystem;
-use crate::utils::read;
+use crate::systems::optimizer::OptimizerSystem;
+use crate::utils::{dump, read};
+
+pub const DEFAULT_READ_DEPTH: u32 = 512;
+pub const APICA_ENTRYPOINTS: [ApicaEntrypointBytecode; 6] = [
+    ApicaEntrypointBytecode::BeforeInit, ApicaEntrypointBytecode::Init,
+    ApicaEntrypointBytecode::BeforeUpdate, ApicaEntrypointBytecode::Update,
+    ApicaEntrypointBytecode::BeforeQuit, ApicaEntrypointBytecode::Quit,
+];
+
+pub fn get_app_filepath(app_name: &str) -> String {
+    format!("apps/{app_name}/{app_name}.apb")
+}
 
 pub struct BytecodeReaderSystem {
     bytecode_nodes: HashMap<u64, NodeCompound>,
     specifications: HashMap<&'static str, Value>,
+    debug_locations: HashMap<u32, SourceLocation>,
+    node_counter: u32,
+    depth: u32,
+    max_depth: u32,
+    read_failed: bool,
 }
 
 impl BytecodeReaderSystem {
     pub fn init() -> BytecodeReaderSystem {
-        BytecodeReaderSystem { bytecode_nodes: HashMap::new(), specifications: HashMap::new() }
+        BytecodeReaderSystem {
+            bytecode_nodes: HashMap::new(),
+            specifications: HashMap::new(),
+            debug_locations: HashMap::new(),
+            node_counter: 0,
+            depth: 0,
+            max_depth: DEFAULT_READ_DEPTH,
+            read_failed: false,
+        }
+    }
+
+    pub fn get_max_depth(&self) -> u32 {
+        self.max_depth
+    }
+
+    pub fn set_max_depth(&mut self, max_depth: u32) {
+        self.max_depth = max_depth;
     }
 
     pub fn clear(&mut self) {
         self.bytecode_nodes.clear();
         self.specifications.clear();
+        self.debug_locations.clear();
+        self.node_counter = 0;
+        self.depth = 0;
+        self.read_failed = false;
     }
 
     pub fn get_entry_node(&self, entry: ApicaEntrypointBytecode) -> Option<&NodeCompound> {
         self.bytecode_nodes.get(&(entry as u64))
     }
 
+    pub fn get_entry_node_mut(&mut self, entry: ApicaEntrypointBytecode) -> Option<&mut NodeCompound> {
+        self.bytecode_nodes.get_mut(&(entry as u64))
+    }
+
     pub fn get_data(&self, key: &str) -> Option<&Value> {
         self.specifications.get(key)
     }
 
-    pub fn read_app(&mut self, app_name: &str, logger: &mut LoggerSystem) {
+    pub fn optimize(&mut self, optimizer: &mut OptimizerSystem) {
+        let entries = std::mem::take(&mut self.bytecode_nodes);
+        self.bytecode_nodes = entries.into_iter().map(|(entry, root)| (entry, optimizer.optimize_entry(root))).collect();
+    }
+
+    pub fn dump_entries(&self) -> String {
+        let mut entries: Vec<_> = self.bytecode_nodes.iter().collect();
+        entries.sort_by_key(|(entry, _)| **entry);
+
+        let mut output = String::new();
+        for (entry, root) in entries {
+            match ApicaEntrypointBytecode::try_from(*entry) {
+                Ok(entry_code) => output.push_str(&format!("Entry {:?}\n", entry_code)),
+                Err(_) => output.push_str(&format!("Entry #{entry}\n")),
+            }
+
+            dump::dump_compound(root, 1, &mut output);
+        }
+
+        output
+    }
+
+    pub fn read_file(&mut self, filepath: &str, app_name: &str, logger: &mut LoggerSystem) -> bool {
         self.clear();
-        let filepath = format!("apps/{app_name}/{app_name}.apb");
         if let Ok(mut input_file) = File::open(filepath) {
-            let mut spec_code = read::read_specification_bytecode(&mut input_file);
-            while let Some(spec_bytecode) = &spec_code && *spec_bytecode != ApicaSpecificationBytecode::EndOfSpecification {
-                self.read_specification(&mut input_file, *spec_bytecode, logger);
-                spec_code = read::read_specification_bytecode(&mut input_file);
-            }
+            self.read_specifications(&mut input_file, logger);
 
             let mut code = read::read_bytecode(&mut input_file);
-            while let Some(bytecode) = &code && *bytecode != ApicaBytecode::EndOfFile {
+            while let Some(bytecode) = &code && *bytecode != ApicaBytecode::EndOfFile && !self.read_failed {
                 if *bytecode == ApicaBytecode::Entrypoint {
                     self.read_entrypoint(&mut input_file, logger);
+                } else if *bytecode == ApicaBytecode::DebugInfo {
+                    self.read_debug_info(&mut input_file, logger);
                 }
 
                 code = read::read_bytecode(&mut input_file);
             }
         } else {
             logger.system_logn_error(format!("Failed to find or open APB file named -> {app_name}"));
+            return false;
+        }
+
+        if self.read_failed {
+            self.bytecode_nodes.clear();
+            logger.system_logn_error(format!("The APB file of `{app_name}` was rejected"));
+            return false;
+        }
+
+        true
+    }
+
+    pub fn read_app_specifications(&mut self, app_name: &str, logger: &mut LoggerSystem) -> bool {
+        self.clear();
+        if let Ok(mut input_file) = File::open(get_app_filepath(app_name)) {
+            self.read_specifications(&mut input_file, logger);
+            true
+        } else {
+            logger.system_logn_error(format!("Failed to find or open APB file named -> {app_name}"));
+            false
+        }
+    }
+
+    fn read_specifications(&mut self, input_file: &mut File, logger: &mut LoggerSystem) {
+        let mut spec_code = read::read_specification_bytecode(input_file);
+        while let Some(spec_bytecode) = &spec_code && *spec_bytecode != ApicaSpecificationBytecode::EndOfSpecification {
+            self.read_specification(input_file, *spec_bytecode, logger);
+            spec_code = read::read_specification_bytecode(input_file);
         }
     }
 
@@ -87,7 +179,7 @@ impl BytecodeReaderSystem {
 
         let mut nodes = vec![];
         let mut actual_code = read::read_bytecode(input_file);
-        while let Some(actual_bytecode) = &actual_code && *actual_bytecode != ApicaBytecode::EndOfBlock {
+        while let Some(actual_bytecode) = &actual_code && *actual_bytecode != ApicaBytecode::EndOfBlock && !self.read_failed {
             if let Some(node) = self.read_node(input_file, *actual_bytecode, logger) {
                 nodes.push(node);
             }
@@ -95,14 +187,115 @@ impl BytecodeReaderSystem {
             actual_code = read::read_bytecode(input_file);
         }
 
+        if self.read_failed {
+            return;
+        }
+
         let _ = self.bytecode_nodes.insert(entry_code as u64, NodeCompound::init(nodes));
         logger.system_logn_success(format!("Entry {:?} was read successfully", entry_code));
     }
 
+    // The debug info maps node indices to source locations, so it must precede every entrypoint.
+    // A malformed section only costs the locations: the app is still loaded without them.
+    fn read_debug_info(&mut self, input_file: &mut File, logger: &mut LoggerSystem) {
+        let misplaced = self.node_counter > 0 || !self.bytecode_nodes.is_empty();
+
+        let file_count = match read::read_u16(input_file) {
+            Some(count) => count,
+            None => {
+                self.reject_debug_info(logger, String::from("Unable to read the file count of the debug info"));
+                return;
+            },
+        };
+
+        let mut files: Vec<Rc<str>> = vec![];
+        for _ in 0..file_count {
+            match read::read_string(input_file) {
+                Some(file) => files.push(Rc::from(file)),
+                None => {
+                    self.reject_debug_info(logger, String::from("Unable to read a file name of the debug info"));
+                    return;
+                },
+            }
+        }
+
+        let location_count = match read::read_u32(input_file) {
+            Some(count) => count,
+            None => {
+                self.reject_debug_info(logger, String::from("Unable to read the location count of the debug info"));
+                return;
+            },
+        };
+
+        let mut unknown_file = None;
+        for _ in 0..location_count {
+            let (node_index, file_index, line, column) = match (
+                read::read_u32(input_file), read::read_u16(input_file), read::read_u32(input_file), read::read_u32(input_file)
+            ) {
+                (Some(node_index), Some(file_index), Some(line), Some(column)) => (node_index, file_index, line, column),
+                _ => {
+                    self.reject_debug_info(logger, String::from("Unable to read a location of the debug info"));
+                    return;
+                },
+            };
+
+            match files.get(file_index as usize) {
+                Some(file) => {
+                    self.debug_locations.insert(node_index, SourceLocation::init(file.clone(), line, column));
+                },
+                None => unknown_file = Some(file_index),
+            }
+        }
+
+        if let Some(file_index) = unknown_file {
+            self.reject_debug_info(logger, format!("A debug location refers to an unknown file -> {file_index}"));
+            return;
+        }
+
+        if misplaced {
+            self.reject_debug_info(logger, String::from("The debug info must come before the entrypoints"));
+            return;
+        }
+
+        logger.system_logn_success(format!("Debug info was read successfully ({location_count} locations)"));
+    }
+
+    fn reject_debug_info(&mut self, logger: &mut LoggerSystem, details: String) {
+        logger.system_logn_error(format!("ReadError: {details}, source locations are disabled"));
+        self.debug_locations.clear();
+    }
+
     fn read_node(&mut self, input_file: &mut File, code: ApicaBytecode, logger: &mut LoggerSystem) -> Option<Node> {
+        if self.read_failed {
+            return None;
+        }
+
+        if self.depth >= self.max_depth {
+            logger.system_logn_error(format!("ReadError: The maximum nesting depth of {} nodes was exceeded", self.max_depth));
+            self.read_failed = true;
+            return None;
+        }
+
+        let node_index = self.node_counter;
+        self.node_counter += 1;
+
+        self.depth += 1;
+        let node = self.read_bare_node(input_file, code, logger);
+        self.depth -= 1;
+
+        let node = node?;
+        if let Some(location) = self.debug_locations.remove(&node_index) {
+            Some(Node::Located(Box::new(NodeLocated::init(location, node))))
+        } else {
+            Some(node)
+        }
+    }
+
+    fn read_bare_node(&mut self, input_file: &mut File, code: ApicaBytecode, logger: &mut LoggerSystem) -> Option<Node> {
         match code {
             ApicaBytecode::Compound => Some(self.read_compound(input_file, logger)),
             ApicaBytecode::BuiltinFuncCall => self.read_builtin_func_call(input_file, logger),
+            ApicaBytecode::NamedFuncCall => self.read_named_func_call(input_file, logger),
             ApicaBytecode::Literal => self.read_literal(input_file, logger),
             ApicaBytecode::Global => self.read_global_scope(input_file, logger),
             ApicaBytecode::VarConstCall => self.read_var_const_call(input_file, logger),
@@ -120,6 +313,8 @@ impl BytecodeReaderSystem {
             ApicaBytecode::If => self.read_if(input_file, logger),
             ApicaBytecode::IfElse => self.read_if_else(input_file, logger),
             ApicaBytecode::While => self.read_while(input_file, logger),
+            ApicaBytecode::Try => self.read_try_catch(input_file, false, logger),
+            ApicaBytecode::TryFinally => self.read_try_catch(input_file, true, logger),
 
             _ => {
                 logger.system_logn_error(format!("An unexpected Apica Bytecode was found -> {:?}", code));
@@ -131,7 +326,7 @@ impl BytecodeReaderSystem {
     fn read_compound(&mut self, input_file: &mut File, logger: &mut LoggerSystem) -> Node {
         let mut nodes = vec![];
         let mut actual_code = read::read_bytecode(input_file);
-        while let Some(actual_bytecode) = &actual_code && *actual_bytecode != ApicaBytecode::EndOfBlock {
+        while let Some(actual_bytecode) = &actual_code && *actual_bytecode != ApicaBytecode::EndOfBlock && !self.read_failed {
             if let Some(node) = self.read_node(input_file, *actual_bytecode, logger) {
                 nodes.push(node);
             }
@@ -153,7 +348,7 @@ impl BytecodeReaderSystem {
 
         let mut parameters = vec![];
         let mut actual_code = read::read_bytecode(input_file);
-        while let Some(actual_bytecode) = &actual_code && *actual_bytecode != ApicaBytecode::EndOfBlock {
+        while let Some(actual_bytecode) = &actual_code && *actual_bytecode != ApicaBytecode::EndOfBlock && !self.read_failed {
             if let Some(node) = self.read_node(input_file, *actual_bytecode, logger) {
                 parameters.push(node);
             }
@@ -164,6 +359,28 @@ impl BytecodeReaderSystem {
         Some(Node::BuiltinFuncCall(NodeBuiltinFuncCall::init(func_bytecode, parameters)))
     }
 
+    fn read_named_func_call(&mut self, input_file: &mut File, logger: &mut LoggerSystem) -> Option<Node> {
+        let name = match read::read_string(input_file) {
+            Some(name) => name,
+            None => {
+                logger.system_logn_error("Unable to read named func-call name".to_string());
+                return None;
+            }
+        };
+
+        let mut parameters = vec![];
+        let mut actual_code = read::read_bytecode(input_file);
+        while let Some(actual_bytecode) = &actual_code && *actual_bytecode != ApicaBytecode::EndOfBlock && !self.read_failed {
+            if let Some(node) = self.read_node(input_file, *actual_bytecode, logger) {
+                parameters.push(node);
+            }
+
+            actual_code = read::read_bytecode(input_file);
+        }
+
+        Some(Node::NamedFuncCall(NodeNamedFuncCall::init(name, parameters)))
+    }
+
     fn read_literal(&mut self, input_file: &mut File, logger: &mut LoggerSystem) -> Option<Node> {
         let type_bytecode = match read::read_type_bytecode(input_file) {
             Some(bytecode) => bytecode,
@@ -232,7 +449,7 @@ impl BytecodeReaderSystem {
     fn read_global_scope(&mut self, input_file: &mut File, logger: &mut LoggerSystem) -> Option<Node> {
         let mut statements = vec![];
         let mut actual_code = read::read_bytecode(input_file);
-        while let Some(actual_bytecode) = &actual_code && *actual_bytecode != ApicaBytecode::EndOfBlock {
+        while let Some(actual_bytecode) = &actual_code && *actual_bytecode != ApicaBytecode::EndOfBlock && !self.read_failed {
             if let Some(node) = self.read_node(input_file, *actual_bytecode, logger) {
                 statements.push(node);
             }
@@ -548,6 +765,69 @@ impl BytecodeReaderSystem {
         Some(Node::While(Box::new(NodeWhile::init(condition, body))))
     }
 
+    fn read_try_catch(&mut self, input_file: &mut File, has_finally: bool, logger: &mut LoggerSystem) -> Option<Node> {
+        let try_body_bytecode = match read::read_bytecode(input_file) {
+            Some(bytecode) => bytecode,
+            None => {
+                logger.system_logn_error("An unknown Apica Bytecode was found for `try-body`".to_string());
+                return None;
+            },
+        };
+
+        let try_body = match self.read_node(input_file, try_body_bytecode, logger) {
+            Some(node) => node,
+            None => return None,
+        };
+
+        let error_name = match read::read_string(input_file) {
+            Some(value) => value,
+            None => {
+                logger.system_logn_error("Unable to read the error name binding of `catch`".to_string());
+                return None;
+            },
+        };
+
+        let error_details = match read::read_string(input_file) {
+            Some(value) => value,
+            None => {
+                logger.system_logn_error("Unable to read the error details binding of `catch`".to_string());
+                return None;
+            },
+        };
+
+        let catch_body_bytecode = match read::read_bytecode(input_file) {
+            Some(bytecode) => bytecode,
+            None => {
+                logger.system_logn_error("An unknown Apica Bytecode was found for `catch-body`".to_string());
+                return None;
+            },
+        };
+
+        let catch_body = match self.read_node(input_file, catch_body_bytecode, logger) {
+            Some(node) => node,
+            None => return None,
+        };
+
+        let finally_body = if has_finally {
+            let finally_body_bytecode = match read::read_bytecode(input_file) {
+                Some(bytecode) => bytecode,
+                None => {
+                    logger.system_logn_error("An unknown Apica Bytecode was found for `finally-body`".to_string());
+                    return None;
+                },
+            };
+
+            match self.read_node(input_file, finally_body_bytecode, logger) {
+                Some(node) => Some(node),
+                None => return None,
+            }
+        } else {
+            None
+        };
+
+        Some(Node::TryCatch(Box::new(NodeTryCatch::init(try_body, error_name, error_details, catch_body, finally_body))))
+    }
+
     fn read_specification(&mut self, input_file: &mut File, code: ApicaSpecificationBytecode, logger: &mut LoggerSystem) {
         match code {
             ApicaSpecificationBytecode::EndOfSpecification => {},
@@ -558,6 +838,8 @@ impl BytecodeReaderSystem {
             ApicaSpecificationBytecode::LoggerActivation => self.read_data_bool(input_file, logger, "logger"),
             ApicaSpecificationBytecode::WindowWidth => self.read_data_u32(input_file, logger, "window_width"),
             ApicaSpecificationBytecode::WindowHeight => self.read_data_u32(input_file, logger, "window_height"),
+            ApicaSpecificationBytecode::ExecutionBudget => self.read_data_u32(input_file, logger, "execution_budget"),
+            ApicaSpecificationBytecode::Capabilities => self.read_data_u32(input_file, logger, "capabilities"),
         }
     }
 
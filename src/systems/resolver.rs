@@ -0,0 +1,243 @@
+﻿use std::collections::HashMap;
+use apica_common::bytecodes::ApicaTypeBytecode;
+use crate::nodes::compound::NodeCompound;
+use crate::nodes::located::SourceLocation;
+use crate::nodes::node::Node;
+use crate::nodes::slot::Slot;
+
+#[derive(Clone)]
+pub struct GlobalDeclaration {
+    name: String,
+    value_kind: ApicaTypeBytecode,
+    is_const: bool,
+}
+
+impl GlobalDeclaration {
+    pub fn init(name: String, value_kind: ApicaTypeBytecode, is_const: bool) -> GlobalDeclaration {
+        GlobalDeclaration { name, value_kind, is_const }
+    }
+
+    pub fn get_name(&self) -> &String {
+        &self.name
+    }
+
+    pub fn get_value_kind(&self) -> &ApicaTypeBytecode {
+        &self.value_kind
+    }
+
+    pub fn is_const(&self) -> bool {
+        self.is_const
+    }
+}
+
+pub struct ResolverSystem {
+    globals: Vec<GlobalDeclaration>,
+    global_slots: HashMap<String, usize>,
+    scopes: Vec<HashMap<String, usize>>,
+    local_count: usize,
+    locations: Vec<SourceLocation>,
+    errors: Vec<String>,
+}
+
+impl ResolverSystem {
+    pub fn init() -> ResolverSystem {
+        ResolverSystem {
+            globals: vec![],
+            global_slots: HashMap::new(),
+            scopes: vec![],
+            local_count: 0,
+            locations: vec![],
+            errors: vec![],
+        }
+    }
+
+    pub fn clear(&mut self) {
+        *self = ResolverSystem::init();
+    }
+
+    pub fn get_globals(&self) -> &Vec<GlobalDeclaration> {
+        &self.globals
+    }
+
+    pub fn match_globals(&self, previous: &ResolverSystem) -> Vec<Option<usize>> {
+        self.globals.iter().map(|global| {
+            previous.global_slots.get(&global.name).copied().filter(|index| {
+                let old = &previous.globals[*index];
+                old.value_kind == global.value_kind && old.is_const == global.is_const
+            })
+        }).collect()
+    }
+
+    pub fn get_errors(&self) -> &Vec<String> {
+        &self.errors
+    }
+
+    pub fn resolve_entry(&mut self, root: &mut NodeCompound) {
+        self.scopes.clear();
+        self.local_count = 0;
+        self.locations.clear();
+        self.resolve_compound(root, false);
+    }
+
+    fn report(&mut self, name: &str, details: String) {
+        match self.locations.last() {
+            Some(location) => self.errors.push(format!("{name}: {details} (at {location})")),
+            None => self.errors.push(format!("{name}: {details}")),
+        }
+    }
+
+    fn resolve_compound(&mut self, compound: &mut NodeCompound, global: bool) {
+        self.scopes.push(HashMap::new());
+        for node in compound.get_nodes_mut() {
+            self.resolve_node(node, global);
+        }
+
+        self.scopes.pop();
+    }
+
+    fn lookup(&self, name: &String, global: bool) -> Option<Slot> {
+        if !global {
+            for scope in self.scopes.iter().rev() {
+                if let Some(index) = scope.get(name) {
+                    return Some(Slot::Local(*index));
+                }
+            }
+        }
+
+        self.global_slots.get(name).map(|index| Slot::Global(*index))
+    }
+
+    fn declare(&mut self, name: &String, value_kind: ApicaTypeBytecode, is_const: bool, global: bool) -> Option<Slot> {
+        if global {
+            if self.global_slots.contains_key(name) {
+                self.report("DeclarationError", format!("A global element with this name already exists -> {name}"));
+                return None;
+            }
+
+            self.global_slots.insert(name.clone(), self.globals.len());
+            self.globals.push(GlobalDeclaration::init(name.clone(), value_kind, is_const));
+            return Some(Slot::Global(self.globals.len() - 1));
+        }
+
+        let index = self.local_count;
+        let scope = self.scopes.last_mut()?;
+        if scope.contains_key(name) {
+            self.report("DeclarationError", format!("An element with this name already exists in this scope -> {name}"));
+            return None;
+        }
+
+        scope.insert(name.clone(), index);
+        self.local_count += 1;
+        Some(Slot::Local(index))
+    }
+
+    fn declare_binding(&mut self, name: &String) -> Option<usize> {
+        if name.is_empty() {
+            return None;
+        }
+
+        match self.declare(name, ApicaTypeBytecode::String, false, false) {
+            Some(Slot::Local(index)) => Some(index),
+            _ => None,
+        }
+    }
+
+    fn resolve_node(&mut self, node: &mut Node, global: bool) {
+        match node {
+            Node::Compound(compound) => self.resolve_compound(compound, global),
+
+            Node::BuiltinFuncCall(builtin) => {
+                for parameter in builtin.get_parameters_mut() {
+                    self.resolve_node(parameter, global);
+                }
+            },
+
+            Node::NamedFuncCall(named) => {
+                for parameter in named.get_parameters_mut() {
+                    self.resolve_node(parameter, global);
+                }
+            },
+
+            Node::GlobalScope(global_scope) => self.resolve_node(global_scope.get_statement_mut(), true),
+
+            Node::VarConstCall(vc_call) => {
+                match self.lookup(vc_call.get_name(), global) {
+                    Some(slot) => vc_call.set_slot(slot),
+                    None => {
+                        let name = vc_call.get_name().clone();
+                        self.report("AccessError", format!("Cannot find a reference to a var/const -> {name}"));
+                    },
+                }
+            },
+
+            Node::VarDecl(var_decl) => {
+                self.resolve_node(var_decl.get_expression_mut(), false);
+                if let Some(slot) = self.declare(var_decl.get_name(), *var_decl.get_value_kind(), false, global) {
+                    var_decl.set_slot(slot);
+                }
+            },
+
+            Node::ConstDecl(const_decl) => {
+                self.resolve_node(const_decl.get_expression_mut(), false);
+                if let Some(slot) = self.declare(const_decl.get_name(), *const_decl.get_value_kind(), true, global) {
+                    const_decl.set_slot(slot);
+                }
+            },
+
+            Node::Add(add) => {
+                self.resolve_node(add.get_left_mut(), global);
+                self.resolve_node(add.get_right_mut(), global);
+            },
+
+            Node::Increment(increment) => self.resolve_node(increment.get_operand_mut(), global),
+            Node::Decrement(decrement) => self.resolve_node(decrement.get_operand_mut(), global),
+            Node::Not(not) => self.resolve_node(not.get_operand_mut(), global),
+            Node::Convert(convert) => self.resolve_node(convert.get_left_mut(), global),
+
+            Node::TernaryOp(ternary) => {
+                self.resolve_node(ternary.get_condition_mut(), false);
+                self.resolve_node(ternary.get_true_expr_mut(), global);
+                self.resolve_node(ternary.get_false_expr_mut(), global);
+            },
+
+            Node::If(_if) => {
+                self.resolve_node(_if.get_condition_mut(), false);
+                self.resolve_node(_if.get_body_mut(), global);
+            },
+
+            Node::IfElse(if_else) => {
+                self.resolve_node(if_else.get_condition_mut(), false);
+                self.resolve_node(if_else.get_if_body_mut(), global);
+                self.resolve_node(if_else.get_else_body_mut(), global);
+            },
+
+            Node::While(_while) => {
+                self.resolve_node(_while.get_condition_mut(), false);
+                self.resolve_node(_while.get_body_mut(), global);
+            },
+
+            Node::TryCatch(try_catch) => {
+                self.resolve_node(try_catch.get_try_body_mut(), global);
+
+                self.scopes.push(HashMap::new());
+                let name_slot = self.declare_binding(try_catch.get_error_name());
+                let details_slot = self.declare_binding(try_catch.get_error_details());
+                try_catch.set_binding_slots(name_slot, details_slot);
+                self.resolve_node(try_catch.get_catch_body_mut(), global);
+                self.scopes.pop();
+
+                if let Some(finally_body) = try_catch.get_finally_body_mut() {
+                    self.resolve_node(finally_body, global);
+                }
+            },
+
+            Node::Located(located) => {
+                self.locations.push(located.get_location().clone());
+                self.resolve_node(located.get_node_mut(), global);
+                self.locations.pop();
+            },
+
+            Node::Literal(_) | Node::Break(_) | Node::Continue(_) | Node::BlankReturn(_) => {},
+        }
+    }
+}
\ No newline at end of file
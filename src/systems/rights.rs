@@ -1,13 +1,17 @@
-﻿use crate::utils::rights::{ApicaMode, ApicaRight};
+﻿use apica_common::element::Element;
+use apica_common::values::error::ValueError;
+use apica_common::values::value::Value;
+use crate::utils::rights::{self, ApicaCapability, ApicaMode, ApicaRight};
 
 pub struct RightSystem {
     mode: ApicaMode,
     right: ApicaRight,
+    capabilities: ApicaCapability,
 }
 
 impl RightSystem {
     pub fn init() -> RightSystem {
-        RightSystem { mode: ApicaMode::SpecialInit, right: ApicaRight::MainMenu }
+        RightSystem { mode: ApicaMode::SpecialInit, right: ApicaRight::MainMenu, capabilities: ApicaCapability::all() }
     }
     
     pub fn get_mode(&self) -> &ApicaMode {
@@ -18,6 +22,10 @@ impl RightSystem {
         self.mode = mode;
     }
     
+    pub fn get_right(&self) -> ApicaRight {
+        self.right
+    }
+
     pub fn set_right(&mut self, right: ApicaRight) {
         self.right = right;
     }
@@ -26,6 +34,25 @@ impl RightSystem {
         self.right |= right;
     }
     
+    pub fn get_capabilities(&self) -> ApicaCapability {
+        self.capabilities
+    }
+
+    pub fn set_capabilities(&mut self, capabilities: ApicaCapability) {
+        self.capabilities = capabilities;
+    }
+
+    pub fn check_capability(&self, capability: ApicaCapability, function: &str) -> Option<Element> {
+        if self.capabilities.contains(capability) {
+            return None;
+        }
+
+        Some(Element::create_error(Value::Error(ValueError::init_with(
+            String::from("PermissionError"),
+            Some(format!("The app needs the `{}` capability to call the function `{function}`", rights::get_capability_name(capability))),
+        ))))
+    }
+
     pub fn is_running(&self) -> bool {
         self.mode != ApicaMode::SpecialQuit
     }
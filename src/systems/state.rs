@@ -0,0 +1,260 @@
+﻿use std::fs::{create_dir_all, File};
+use apica_common::bytecodes::ApicaTypeBytecode;
+use apica_common::element::{Element, ElementModifier};
+use apica_common::values::error::ValueError;
+use apica_common::values::value::Value;
+use crate::systems::resolver::GlobalDeclaration;
+use crate::utils::{read, write};
+
+pub const APICA_STATE_MAGIC: u32 = 0x54535041;
+pub const APICA_STATE_VERSION: u16 = 1;
+
+#[derive(Copy, Clone)]
+pub enum StateRequest {
+    Save(u32),
+    Load(u32),
+}
+
+struct SavedGlobal {
+    name: String,
+    value_kind: ApicaTypeBytecode,
+    is_const: bool,
+    element: Option<Element>,
+}
+
+pub struct StateSystem {
+    app_id: String,
+    declarations: Vec<GlobalDeclaration>,
+    request: Option<StateRequest>,
+}
+
+impl StateSystem {
+    pub fn init() -> StateSystem {
+        StateSystem { app_id: String::new(), declarations: vec![], request: None }
+    }
+
+    pub fn set_app(&mut self, app_id: &str, declarations: &[GlobalDeclaration]) {
+        self.app_id = app_id.to_string();
+        self.declarations = declarations.to_vec();
+        self.request = None;
+    }
+
+    pub fn request(&mut self, request: StateRequest) {
+        self.request = Some(request);
+    }
+
+    pub fn take_request(&mut self) -> Option<StateRequest> {
+        self.request.take()
+    }
+
+    pub fn execute(&self, request: StateRequest, globals: &mut Vec<Option<Element>>) -> Element {
+        match request {
+            StateRequest::Save(slot) => self.save(slot, globals),
+            StateRequest::Load(slot) => self.load(slot, globals),
+        }
+    }
+
+    fn get_filepath(&self, slot: u32) -> String {
+        format!("data/{}/state_{slot}.aps", self.app_id)
+    }
+
+    fn save(&self, slot: u32, globals: &[Option<Element>]) -> Element {
+        if create_dir_all(format!("data/{}", self.app_id)).is_err() {
+            return state_error(format!("Cannot create the data directory of `{}`", self.app_id));
+        }
+
+        let mut content = vec![];
+        if !self.write_globals(&mut content, globals) || !write::write_file_atomically(&self.get_filepath(slot), &content) {
+            return state_error(format!("Cannot write the state slot {slot}"));
+        }
+
+        Element::create_null()
+    }
+
+    fn write_globals(&self, output: &mut Vec<u8>, globals: &[Option<Element>]) -> bool {
+        if !write::write_u32(output, APICA_STATE_MAGIC) || !write::write_u16(output, APICA_STATE_VERSION)
+            || !write::write_u32(output, self.declarations.len() as u32) {
+            return false;
+        }
+
+        for (index, declaration) in self.declarations.iter().enumerate() {
+            let element = globals.get(index).and_then(Option::as_ref);
+            let written = write::write_string(output, declaration.get_name())
+                && write::write_type_bytecode(output, *declaration.get_value_kind())
+                && write::write_u8(output, declaration.is_const() as u8)
+                && write::write_u8(output, element.is_some() as u8)
+                && element.is_none_or(|element| write::write_value(output, element.get_value()));
+
+            if !written {
+                return false;
+            }
+        }
+
+        true
+    }
+
+    fn load(&self, slot: u32, globals: &mut Vec<Option<Element>>) -> Element {
+        match File::open(self.get_filepath(slot)) {
+            Ok(mut file) => self.restore(slot, &mut file, globals),
+            Err(_) => state_error(format!("There is no saved state in the slot {slot}")),
+        }
+    }
+
+    fn restore(&self, slot: u32, file: &mut File, globals: &mut Vec<Option<Element>>) -> Element {
+        if read::read_u32(file) != Some(APICA_STATE_MAGIC) {
+            return state_error(format!("The state slot {slot} is not a valid state file"));
+        }
+
+        match read::read_u16(file) {
+            Some(APICA_STATE_VERSION) => {},
+            Some(version) => return state_error(format!("The state slot {slot} has an unsupported version -> {version}")),
+            None => return state_error(format!("The state slot {slot} is truncated")),
+        }
+
+        let saved_globals = match read_globals(file) {
+            Some(saved_globals) => saved_globals,
+            None => return state_error(format!("The state slot {slot} is truncated")),
+        };
+
+        let mut restored = vec![];
+        let mut mismatches = vec![];
+        for saved in saved_globals {
+            let index = match self.declarations.iter().position(|declaration| *declaration.get_name() == saved.name) {
+                Some(index) => index,
+                None => {
+                    mismatches.push(format!("`{}` is no longer declared", saved.name));
+                    continue;
+                }
+            };
+
+            let declaration = &self.declarations[index];
+            if *declaration.get_value_kind() != saved.value_kind {
+                mismatches.push(format!("`{}` was saved as {:?} but is declared as {:?}", saved.name, saved.value_kind, declaration.get_value_kind()));
+            } else if declaration.is_const() != saved.is_const {
+                mismatches.push(format!("`{}` was saved as {} but is declared as {}", saved.name, describe_const(saved.is_const), describe_const(declaration.is_const())));
+            } else {
+                restored.push((index, saved.element));
+            }
+        }
+
+        if !mismatches.is_empty() {
+            return state_error(format!("The state slot {slot} does not match the app -> {}", mismatches.join(", ")));
+        }
+
+        if globals.len() < self.declarations.len() {
+            globals.resize_with(self.declarations.len(), || None);
+        }
+
+        for (index, element) in restored {
+            globals[index] = element;
+        }
+
+        Element::create_null()
+    }
+}
+
+fn read_globals(file: &mut File) -> Option<Vec<SavedGlobal>> {
+    let count = read::read_u32(file)?;
+    let mut saved_globals = vec![];
+    for _ in 0..count {
+        let name = read::read_string(file)?;
+        let value_kind = read::read_type_bytecode(file)?;
+        let is_const = read::read_u8(file)? != 0;
+        let modifier = if is_const { ElementModifier::Const } else { ElementModifier::None };
+        let element = if read::read_u8(file)? != 0 {
+            Some(Element::init(modifier, read::read_value(file)?))
+        } else {
+            None
+        };
+
+        saved_globals.push(SavedGlobal { name, value_kind, is_const, element });
+    }
+
+    Some(saved_globals)
+}
+
+fn describe_const(is_const: bool) -> &'static str {
+    if is_const { "a const" } else { "a var" }
+}
+
+fn state_error(details: String) -> Element {
+    Element::create_error(Value::Error(ValueError::init_with(
+        String::from("StateError"),
+        Some(details),
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::remove_file;
+    use apica_common::values::string::ValueString;
+    use apica_common::values::u32::ValueU32;
+    use crate::utils::format;
+    use super::*;
+
+    fn declarations(score_kind: ApicaTypeBytecode) -> Vec<GlobalDeclaration> {
+        vec![
+            GlobalDeclaration::init(String::from("score"), score_kind, false),
+            GlobalDeclaration::init(String::from("player"), ApicaTypeBytecode::String, true),
+        ]
+    }
+
+    fn globals() -> Vec<Option<Element>> {
+        vec![
+            Some(Element::init(ElementModifier::None, Value::U32(ValueU32::init_with(42)))),
+            Some(Element::init(ElementModifier::Const, Value::String(ValueString::init_with(String::from("apica"))))),
+        ]
+    }
+
+    fn restore_saved(name: &str, saved_with: ApicaTypeBytecode, loaded_with: ApicaTypeBytecode, restored: &mut Vec<Option<Element>>) -> Element {
+        let mut saving = StateSystem::init();
+        saving.set_app("test", &declarations(saved_with));
+        let mut content = vec![];
+        assert!(saving.write_globals(&mut content, &globals()));
+
+        let filepath = std::env::temp_dir().join(format!("apica_state_{name}_{}.aps", std::process::id()));
+        std::fs::write(&filepath, &content).unwrap();
+
+        let mut loading = StateSystem::init();
+        loading.set_app("test", &declarations(loaded_with));
+        let result = loading.restore(0, &mut File::open(&filepath).unwrap(), restored);
+        let _ = remove_file(&filepath);
+        result
+    }
+
+    #[test]
+    fn saved_globals_are_restored() {
+        let mut restored = vec![];
+        let result = restore_saved("round_trip", ApicaTypeBytecode::U32, ApicaTypeBytecode::U32, &mut restored);
+
+        assert!(!result.is_error_or_controller());
+        let values: Vec<String> = restored.iter().map(|global| format::element_to_string(global.as_ref().unwrap())).collect();
+        assert_eq!(values, vec![String::from("42"), String::from("apica")]);
+    }
+
+    #[test]
+    fn constant_globals_stay_constant() {
+        let mut restored = vec![];
+        let result = restore_saved("constant", ApicaTypeBytecode::U32, ApicaTypeBytecode::U32, &mut restored);
+
+        assert!(!result.is_error_or_controller());
+        assert!(!restored[0].as_ref().unwrap().get_modifier().contains(ElementModifier::Const));
+        assert!(restored[1].as_ref().unwrap().get_modifier().contains(ElementModifier::Const));
+    }
+
+    #[test]
+    fn type_mismatches_are_reported_without_touching_the_globals() {
+        let mut restored = vec![None, None];
+        let result = restore_saved("mismatch", ApicaTypeBytecode::U32, ApicaTypeBytecode::String, &mut restored);
+
+        match result.get_value() {
+            Value::Error(error) => {
+                assert_eq!(error.get_name().as_deref(), Some("StateError"));
+                assert!(error.get_details().as_ref().unwrap().contains("`score` was saved as"));
+            },
+            _ => panic!("a mismatching state slot must raise a StateError"),
+        }
+
+        assert!(restored.iter().all(Option::is_none));
+    }
+}
\ No newline at end of file
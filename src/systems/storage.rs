@@ -0,0 +1,242 @@
+﻿use std::collections::BTreeMap;
+use std::fs::{create_dir_all, File};
+use apica_common::element::{Element, ElementModifier};
+use apica_common::values::bool::ValueBool;
+use apica_common::values::error::ValueError;
+use apica_common::values::string::ValueString;
+use apica_common::values::value::Value;
+use crate::utils::{read, write};
+
+pub const APICA_STORE_MAGIC: u32 = 0x564B5041;
+pub const APICA_STORE_VERSION: u16 = 1;
+pub const DEFAULT_STORAGE_QUOTA: u64 = 64 * 1024;
+
+pub struct StorageSystem {
+    app_id: Option<String>,
+    entries: BTreeMap<String, Value>,
+    quota: u64,
+}
+
+impl StorageSystem {
+    pub fn init(quota: u64) -> StorageSystem {
+        StorageSystem { app_id: None, entries: BTreeMap::new(), quota }
+    }
+
+    pub fn set_app(&mut self, app_id: Option<&str>) -> bool {
+        self.app_id = app_id.map(|app_id| app_id.to_string());
+        self.entries.clear();
+
+        let mut file = match self.app_id.as_ref().and_then(|app_id| File::open(get_filepath(app_id)).ok()) {
+            Some(file) => file,
+            None => return true,
+        };
+
+        match read_entries(&mut file) {
+            Some(entries) => {
+                self.entries = entries;
+                true
+            },
+            None => false,
+        }
+    }
+
+    pub fn set(&mut self, key: &str, value: &Value) -> Element {
+        if let Some(error) = self.check_key(key) {
+            return error;
+        }
+
+        // Strings are stored NUL-terminated, so an embedded NUL would corrupt every later entry.
+        if let Value::String(string) = value && string.get_value().as_ref().is_some_and(|string| string.contains('\0')) {
+            return storage_error(format!("The value stored under `{key}` contains a NUL character"));
+        }
+
+        let previous = self.entries.insert(key.to_string(), value.clone());
+        let result = self.flush();
+        if result.is_error_or_controller() {
+            match previous {
+                Some(previous) => self.entries.insert(key.to_string(), previous),
+                None => self.entries.remove(key),
+            };
+        }
+
+        result
+    }
+
+    pub fn get(&self, key: &str, default: Option<&Element>) -> Element {
+        if let Some(error) = self.check_key(key) {
+            return error;
+        }
+
+        match self.entries.get(key) {
+            Some(value) => Element::init(ElementModifier::None, value.clone()),
+            None => default.map(|default| Element::init(ElementModifier::None, default.get_value().clone())).unwrap_or_else(Element::create_null),
+        }
+    }
+
+    pub fn delete(&mut self, key: &str) -> Element {
+        if let Some(error) = self.check_key(key) {
+            return error;
+        }
+
+        let previous = match self.entries.remove(key) {
+            Some(previous) => previous,
+            None => return Element::init(ElementModifier::None, Value::Bool(ValueBool::init_with(false))),
+        };
+
+        let result = self.flush();
+        if result.is_error_or_controller() {
+            self.entries.insert(key.to_string(), previous);
+            return result;
+        }
+
+        Element::init(ElementModifier::None, Value::Bool(ValueBool::init_with(true)))
+    }
+
+    pub fn keys(&self) -> Element {
+        if self.app_id.is_none() {
+            return missing_id_error();
+        }
+
+        let keys = self.entries.keys().cloned().collect::<Vec<String>>().join("\n");
+        Element::init(ElementModifier::None, Value::String(ValueString::init_with(keys)))
+    }
+
+    fn check_key(&self, key: &str) -> Option<Element> {
+        if self.app_id.is_none() {
+            return Some(missing_id_error());
+        }
+
+        if key.is_empty() || key.chars().any(char::is_control) {
+            return Some(storage_error(format!("Invalid storage key -> `{key}`")));
+        }
+
+        None
+    }
+
+    fn flush(&self) -> Element {
+        let app_id = match &self.app_id {
+            Some(app_id) => app_id,
+            None => return missing_id_error(),
+        };
+
+        let mut content = vec![];
+        if !write_entries(&mut content, &self.entries) {
+            return storage_error(String::from("A stored value has a type that cannot be persisted"));
+        }
+
+        if content.len() as u64 > self.quota {
+            return storage_error(format!("The storage quota of {} bytes would be exceeded ({} bytes)", self.quota, content.len()));
+        }
+
+        if create_dir_all(format!("data/{app_id}")).is_err() || !write::write_file_atomically(&get_filepath(app_id), &content) {
+            return storage_error(format!("Cannot write the storage of `{app_id}`"));
+        }
+
+        Element::create_null()
+    }
+}
+
+fn get_filepath(app_id: &str) -> String {
+    format!("data/{app_id}/store.akv")
+}
+
+fn read_entries(file: &mut File) -> Option<BTreeMap<String, Value>> {
+    if read::read_u32(file)? != APICA_STORE_MAGIC || read::read_u16(file)? != APICA_STORE_VERSION {
+        return None;
+    }
+
+    let count = read::read_u32(file)?;
+    let mut entries = BTreeMap::new();
+    for _ in 0..count {
+        let key = read::read_string(file)?;
+        let value = read::read_value(file)?;
+        entries.insert(key, value);
+    }
+
+    Some(entries)
+}
+
+fn write_entries(output: &mut Vec<u8>, entries: &BTreeMap<String, Value>) -> bool {
+    if !write::write_u32(output, APICA_STORE_MAGIC) || !write::write_u16(output, APICA_STORE_VERSION)
+        || !write::write_u32(output, entries.len() as u32) {
+        return false;
+    }
+
+    entries.iter().all(|(key, value)| write::write_string(output, key) && write::write_value(output, value))
+}
+
+fn missing_id_error() -> Element {
+    storage_error(String::from("The app has no valid `id` specification, so it cannot use the storage"))
+}
+
+fn storage_error(details: String) -> Element {
+    Element::create_error(Value::Error(ValueError::init_with(
+        String::from("StorageError"),
+        Some(details),
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::remove_file;
+    use apica_common::values::u32::ValueU32;
+    use super::*;
+
+    fn text(value: &str) -> Value {
+        Value::String(ValueString::init_with(value.to_string()))
+    }
+
+    fn error_name(element: &Element) -> Option<String> {
+        match element.get_value() {
+            Value::Error(error) => error.get_name().clone(),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn exceeding_the_quota_is_rejected_and_rolled_back() {
+        let mut storage = StorageSystem::init(32);
+        assert!(storage.set_app(Some("apica_storage_quota_test")));
+
+        let result = storage.set("save", &text(&"x".repeat(64)));
+        assert_eq!(error_name(&result).as_deref(), Some("StorageError"));
+        assert!(matches!(storage.get("save", None).get_value(), Value::Null(_)));
+    }
+
+    #[test]
+    fn strings_with_a_nul_character_are_rejected() {
+        let mut storage = StorageSystem::init(DEFAULT_STORAGE_QUOTA);
+        assert!(storage.set_app(Some("apica_storage_nul_test")));
+
+        assert_eq!(error_name(&storage.set("name", &text("api\0ca"))).as_deref(), Some("StorageError"));
+        assert_eq!(error_name(&storage.set("na\0me", &text("apica"))).as_deref(), Some("StorageError"));
+        assert!(matches!(storage.get("name", None).get_value(), Value::Null(_)));
+        assert!(!write::write_string(&mut Vec::<u8>::new(), "api\0ca"));
+    }
+
+    #[test]
+    fn storage_requires_an_app_id() {
+        let mut storage = StorageSystem::init(DEFAULT_STORAGE_QUOTA);
+        assert!(storage.set_app(None));
+        assert_eq!(error_name(&storage.set("key", &text("value"))).as_deref(), Some("StorageError"));
+    }
+
+    #[test]
+    fn entries_round_trip_through_the_store_format() {
+        let mut entries = BTreeMap::new();
+        entries.insert(String::from("name"), text("apica"));
+        entries.insert(String::from("level"), Value::U32(ValueU32::init_with(7)));
+
+        let mut content = vec![];
+        assert!(write_entries(&mut content, &entries));
+
+        let filepath = std::env::temp_dir().join(format!("apica_store_{}.akv", std::process::id()));
+        assert!(write::write_file_atomically(filepath.to_str().unwrap(), &content));
+        let restored = read_entries(&mut File::open(&filepath).unwrap()).unwrap();
+        let _ = remove_file(&filepath);
+
+        assert_eq!(restored.keys().collect::<Vec<_>>(), vec!["level", "name"]);
+        assert!(matches!(restored.get("level"), Some(Value::U32(level)) if level.get_value() == Some(7)));
+        assert!(matches!(restored.get("name"), Some(Value::String(name)) if name.get_value().as_deref() == Some("apica")));
+    }
+}
\ No newline at end of file
@@ -0,0 +1,124 @@
+﻿use std::collections::HashMap;
+use std::fs::read_to_string;
+
+pub const APICA_CONFIG_FILE: &str = "apica.cfg";
+pub const APICA_POLICY_FILE: &str = "policy.cfg";
+
+#[derive(PartialEq, Copy, Clone)]
+pub enum TimeoutPolicy {
+    SkipFrame,
+    QuitApp,
+}
+
+impl TimeoutPolicy {
+    pub fn parse(value: &str) -> Option<TimeoutPolicy> {
+        match value {
+            "skip_frame" => Some(TimeoutPolicy::SkipFrame),
+            "quit_app" => Some(TimeoutPolicy::QuitApp),
+            _ => None,
+        }
+    }
+}
+
+#[derive(PartialEq, Copy, Clone, Debug)]
+pub enum ExecutionEngine {
+    TreeWalker,
+    VirtualMachine,
+}
+
+impl ExecutionEngine {
+    pub fn parse(value: &str) -> Option<ExecutionEngine> {
+        match value {
+            "tree" => Some(ExecutionEngine::TreeWalker),
+            "vm" => Some(ExecutionEngine::VirtualMachine),
+            _ => None,
+        }
+    }
+}
+
+#[derive(PartialEq, PartialOrd, Copy, Clone)]
+pub enum LogLevel {
+    Info,
+    Success,
+    Warning,
+    Error,
+}
+
+impl LogLevel {
+    pub fn parse(value: &str) -> Option<LogLevel> {
+        match value {
+            "info" => Some(LogLevel::Info),
+            "success" => Some(LogLevel::Success),
+            "warning" => Some(LogLevel::Warning),
+            "error" => Some(LogLevel::Error),
+            _ => None,
+        }
+    }
+}
+
+#[derive(PartialEq, Copy, Clone)]
+pub enum LogFormat {
+    Ansi,
+    Plain,
+    JsonLines,
+}
+
+impl LogFormat {
+    pub fn parse(value: &str) -> Option<LogFormat> {
+        match value {
+            "ansi" => Some(LogFormat::Ansi),
+            "plain" => Some(LogFormat::Plain),
+            "json" => Some(LogFormat::JsonLines),
+            _ => None,
+        }
+    }
+}
+
+pub struct SystemConfig {
+    values: HashMap<String, String>,
+}
+
+impl SystemConfig {
+    pub fn init() -> SystemConfig {
+        SystemConfig { values: HashMap::new() }
+    }
+
+    pub fn load(filepath: &str) -> SystemConfig {
+        match read_to_string(filepath) {
+            Ok(content) => SystemConfig::parse(&content),
+            Err(_) => SystemConfig::init(),
+        }
+    }
+
+    pub fn parse(content: &str) -> SystemConfig {
+        let mut config = SystemConfig::init();
+        for line in content.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            if let Some((key, value)) = line.split_once('=') {
+                config.values.insert(key.trim().to_string(), value.trim().to_string());
+            }
+        }
+
+        config
+    }
+
+    pub fn get_string(&self, key: &str) -> Option<&String> {
+        self.values.get(key)
+    }
+
+    pub fn get_u64(&self, key: &str) -> Option<u64> {
+        self.values.get(key).and_then(|value| value.parse().ok())
+    }
+
+    pub fn get_bool(&self, key: &str) -> Option<bool> {
+        match self.values.get(key).map(|value| value.as_str()) {
+            Some("true") | Some("1") | Some("yes") => Some(true),
+            Some("false") | Some("0") | Some("no") => Some(false),
+            _ => None,
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,138 @@
+﻿use apica_common::element::{Element, ElementModifier};
+use apica_common::values::value::Value;
+use crate::nodes::compound::NodeCompound;
+use crate::nodes::node::Node;
+use crate::utils::format;
+
+fn dump_value(value: &Value) -> String {
+    let text = format::element_to_string(&Element::init(ElementModifier::None, value.clone()));
+    if let Value::String(_) = value {
+        format!("{text:?}")
+    } else {
+        text
+    }
+}
+
+fn dump_line(output: &mut String, depth: usize, line: &str) {
+    output.push_str(&"  ".repeat(depth));
+    output.push_str(line);
+    output.push('\n');
+}
+
+pub fn dump_compound(compound: &NodeCompound, depth: usize, output: &mut String) {
+    for node in compound.get_nodes() {
+        dump_node(node, depth, output);
+    }
+}
+
+pub fn dump_node(node: &Node, depth: usize, output: &mut String) {
+    match node {
+        Node::Compound(compound) => {
+            dump_line(output, depth, "Compound");
+            dump_compound(compound, depth + 1, output);
+        },
+
+        Node::Literal(literal) => dump_line(output, depth, &format!("Literal {}", dump_value(literal.get_value()))),
+
+        Node::BuiltinFuncCall(builtin) => {
+            dump_line(output, depth, &format!("BuiltinFuncCall {:?}", builtin.get_function_bytecode()));
+            for parameter in builtin.get_parameters() {
+                dump_node(parameter, depth + 1, output);
+            }
+        },
+
+        Node::NamedFuncCall(named) => {
+            dump_line(output, depth, &format!("NamedFuncCall {}", named.get_name()));
+            for parameter in named.get_parameters() {
+                dump_node(parameter, depth + 1, output);
+            }
+        },
+
+        Node::GlobalScope(global_scope) => {
+            dump_line(output, depth, "GlobalScope");
+            dump_node(global_scope.get_statement(), depth + 1, output);
+        },
+
+        Node::VarConstCall(vc_call) => dump_line(output, depth, &format!("VarConstCall {}", vc_call.get_name())),
+
+        Node::VarDecl(var_decl) => {
+            dump_line(output, depth, &format!("VarDecl {} : {:?}", var_decl.get_name(), var_decl.get_value_kind()));
+            dump_node(var_decl.get_expression(), depth + 1, output);
+        },
+
+        Node::ConstDecl(const_decl) => {
+            dump_line(output, depth, &format!("ConstDecl {} : {:?}", const_decl.get_name(), const_decl.get_value_kind()));
+            dump_node(const_decl.get_expression(), depth + 1, output);
+        },
+
+        Node::Add(add) => {
+            dump_line(output, depth, "Add");
+            dump_node(add.get_left(), depth + 1, output);
+            dump_node(add.get_right(), depth + 1, output);
+        },
+
+        Node::Increment(increment) => {
+            dump_line(output, depth, "Increment");
+            dump_node(increment.get_operand(), depth + 1, output);
+        },
+
+        Node::Decrement(decrement) => {
+            dump_line(output, depth, "Decrement");
+            dump_node(decrement.get_operand(), depth + 1, output);
+        },
+
+        Node::Not(not) => {
+            dump_line(output, depth, "Not");
+            dump_node(not.get_operand(), depth + 1, output);
+        },
+
+        Node::TernaryOp(ternary) => {
+            dump_line(output, depth, "TernaryOp");
+            dump_node(ternary.get_condition(), depth + 1, output);
+            dump_node(ternary.get_true_expr(), depth + 1, output);
+            dump_node(ternary.get_false_expr(), depth + 1, output);
+        },
+
+        Node::Convert(convert) => {
+            dump_line(output, depth, &format!("Convert {:?}", convert.get_right()));
+            dump_node(convert.get_left(), depth + 1, output);
+        },
+
+        Node::If(_if) => {
+            dump_line(output, depth, "If");
+            dump_node(_if.get_condition(), depth + 1, output);
+            dump_node(_if.get_body(), depth + 1, output);
+        },
+
+        Node::IfElse(if_else) => {
+            dump_line(output, depth, "IfElse");
+            dump_node(if_else.get_condition(), depth + 1, output);
+            dump_node(if_else.get_if_body(), depth + 1, output);
+            dump_node(if_else.get_else_body(), depth + 1, output);
+        },
+
+        Node::While(_while) => {
+            dump_line(output, depth, "While");
+            dump_node(_while.get_condition(), depth + 1, output);
+            dump_node(_while.get_body(), depth + 1, output);
+        },
+
+        Node::TryCatch(try_catch) => {
+            dump_line(output, depth, &format!("TryCatch {} {}", try_catch.get_error_name(), try_catch.get_error_details()));
+            dump_node(try_catch.get_try_body(), depth + 1, output);
+            dump_node(try_catch.get_catch_body(), depth + 1, output);
+            if let Some(finally_body) = try_catch.get_finally_body() {
+                dump_node(finally_body, depth + 1, output);
+            }
+        },
+
+        Node::Break(_) => dump_line(output, depth, "Break"),
+        Node::Continue(_) => dump_line(output, depth, "Continue"),
+        Node::BlankReturn(_) => dump_line(output, depth, "BlankReturn"),
+
+        Node::Located(located) => {
+            dump_line(output, depth, &format!("@ {}", located.get_location()));
+            dump_node(located.get_node(), depth + 1, output);
+        },
+    }
+}
\ No newline at end of file
@@ -0,0 +1,229 @@
+﻿use apica_common::bytecodes::ApicaTypeBytecode;
+use apica_common::element::Element;
+use apica_common::values::value::Value;
+
+#[derive(PartialEq, Copy, Clone)]
+enum FormatAlignment {
+    Left,
+    Center,
+    Right,
+}
+
+struct FormatSpecifier {
+    fill: char,
+    alignment: Option<FormatAlignment>,
+    zero_padding: bool,
+    width: usize,
+    precision: Option<usize>,
+    hexadecimal: Option<bool>,
+}
+
+impl FormatSpecifier {
+    fn init() -> FormatSpecifier {
+        FormatSpecifier { fill: ' ', alignment: None, zero_padding: false, width: 0, precision: None, hexadecimal: None }
+    }
+
+    fn parse(specifier: &str) -> Result<FormatSpecifier, String> {
+        let mut result = FormatSpecifier::init();
+        let chars: Vec<char> = specifier.chars().collect();
+        let mut index = 0;
+
+        let alignment_of = |c: char| match c {
+            '<' => Some(FormatAlignment::Left),
+            '^' => Some(FormatAlignment::Center),
+            '>' => Some(FormatAlignment::Right),
+            _ => None,
+        };
+
+        if chars.len() >= 2 && let Some(alignment) = alignment_of(chars[1]) {
+            result.fill = chars[0];
+            result.alignment = Some(alignment);
+            index = 2;
+        } else if let Some(first) = chars.first() && let Some(alignment) = alignment_of(*first) {
+            result.alignment = Some(alignment);
+            index = 1;
+        }
+
+        if index < chars.len() && chars[index] == '0' {
+            result.zero_padding = true;
+            index += 1;
+        }
+
+        let width_start = index;
+        while index < chars.len() && chars[index].is_ascii_digit() {
+            index += 1;
+        }
+        if index > width_start {
+            result.width = chars[width_start..index].iter().collect::<String>().parse().unwrap_or(0);
+        }
+
+        if index < chars.len() && chars[index] == '.' {
+            index += 1;
+            let precision_start = index;
+            while index < chars.len() && chars[index].is_ascii_digit() {
+                index += 1;
+            }
+            if index == precision_start {
+                return Err(format!("Missing precision after `.` in specifier -> {{:{specifier}}}"));
+            }
+            result.precision = chars[precision_start..index].iter().collect::<String>().parse().ok();
+        }
+
+        if index < chars.len() {
+            match chars[index] {
+                'x' => result.hexadecimal = Some(false),
+                'X' => result.hexadecimal = Some(true),
+                _ => return Err(format!("Unknown format type `{}` in specifier -> {{:{specifier}}}", chars[index])),
+            }
+            index += 1;
+        }
+
+        if index < chars.len() {
+            return Err(format!("Unexpected characters in specifier -> {{:{specifier}}}"));
+        }
+
+        Ok(result)
+    }
+
+    fn apply(&self, element: &Element) -> Result<String, String> {
+        let mut text = element_to_string(element);
+        let is_number = !matches!(element.get_value(), Value::String(_)) && text.parse::<f64>().is_ok();
+
+        if let Some(uppercase) = self.hexadecimal {
+            let integer = match element.get_value() {
+                Value::U8(value) => value.get_value().map(i128::from),
+                Value::U32(value) => value.get_value().map(i128::from),
+                Value::U64(value) => value.get_value().map(i128::from),
+                Value::I64(value) => value.get_value().map(i128::from),
+                Value::F64(value) => value.get_value()
+                    .filter(|value| value.fract() == 0.0 && value.abs() < 2f64.powi(63))
+                    .map(|value| value as i128),
+                _ => None,
+            };
+
+            let integer = match integer {
+                Some(value) => value,
+                None => return Err(format!("Cannot format a non-integer value as hexadecimal -> {text}")),
+            };
+
+            let digits = if uppercase { format!("{:X}", integer.unsigned_abs()) } else { format!("{:x}", integer.unsigned_abs()) };
+            text = if integer < 0 { format!("-{digits}") } else { digits };
+        } else if let Some(precision) = self.precision {
+            if is_number {
+                text = format!("{:.precision$}", text.parse::<f64>().unwrap_or(0.0));
+            } else {
+                text = text.chars().take(precision).collect();
+            }
+        }
+
+        let length = text.chars().count();
+        if length >= self.width {
+            return Ok(text);
+        }
+
+        let padding = self.width - length;
+        if self.zero_padding && self.alignment.is_none() {
+            let (sign, digits) = match text.strip_prefix('-') {
+                Some(digits) => ("-", digits.to_string()),
+                None => ("", text),
+            };
+
+            return Ok(format!("{sign}{}{digits}", "0".repeat(padding)));
+        }
+
+        let fill = self.fill.to_string();
+        let alignment = self.alignment.unwrap_or(if is_number { FormatAlignment::Right } else { FormatAlignment::Left });
+        let (before, after) = match alignment {
+            FormatAlignment::Left => (0, padding),
+            FormatAlignment::Center => (padding / 2, padding - padding / 2),
+            FormatAlignment::Right => (padding, 0),
+        };
+
+        Ok(format!("{}{text}{}", fill.repeat(before), fill.repeat(after)))
+    }
+}
+
+pub fn element_to_string(element: &Element) -> String {
+    if let Value::String(string) = element.get_value() {
+        return string.get_value().clone().unwrap_or(String::from("null"));
+    }
+
+    let converted = element.convert(ApicaTypeBytecode::String);
+    if let Value::String(string) = converted.get_value() {
+        string.get_value().clone().unwrap_or(String::from("null"))
+    } else {
+        String::from("null")
+    }
+}
+
+pub fn format_template(template: &str, arguments: &[Element]) -> Result<String, String> {
+    let mut result = String::new();
+    let mut next_position = 0;
+    let mut chars = template.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                result.push('{');
+            },
+
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                result.push('}');
+            },
+
+            '{' => {
+                let mut placeholder = String::new();
+                let mut closed = false;
+                for inner in chars.by_ref() {
+                    if inner == '}' {
+                        closed = true;
+                        break;
+                    }
+                    placeholder.push(inner);
+                }
+
+                if !closed {
+                    return Err(format!("Unclosed placeholder in template -> {template}"));
+                }
+
+                let (position, specifier) = match placeholder.split_once(':') {
+                    Some((position, specifier)) => (position, specifier),
+                    None => (placeholder.as_str(), ""),
+                };
+
+                let index = if position.is_empty() {
+                    next_position += 1;
+                    next_position - 1
+                } else if let Ok(index) = position.parse::<usize>() {
+                    index
+                } else {
+                    return Err(format!("Invalid placeholder position -> {{{placeholder}}}"));
+                };
+
+                let argument = match arguments.get(index) {
+                    Some(argument) => argument,
+                    None => return Err(format!("Placeholder {{{placeholder}}} refers to a missing argument -> {index}")),
+                };
+
+                result.push_str(&FormatSpecifier::parse(specifier)?.apply(argument)?);
+            },
+
+            '}' => return Err(format!("Unmatched `}}` in template -> {template}")),
+
+            _ => result.push(c),
+        }
+    }
+
+    Ok(result)
+}
+
+pub fn format_parameters(parameters: Vec<Element>) -> Result<String, String> {
+    let template = match parameters.first() {
+        Some(template) => element_to_string(template),
+        None => return Err(String::from("Incorrect arguments passed to the function `Format`")),
+    };
+
+    format_template(&template, &parameters[1..])
+}
\ No newline at end of file
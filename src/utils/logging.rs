@@ -0,0 +1,115 @@
+﻿use crate::utils::config::{LogFormat, LogLevel};
+
+pub struct LogRecord<'a> {
+    timestamp: String,
+    level: LogLevel,
+    system: bool,
+    app_id: &'a str,
+    frame: u64,
+    message: &'a str,
+}
+
+impl<'a> LogRecord<'a> {
+    pub fn init(timestamp: String, level: LogLevel, system: bool, app_id: &'a str, frame: u64, message: &'a str) -> LogRecord<'a> {
+        LogRecord { timestamp, level, system, app_id, frame, message }
+    }
+
+    fn get_tag(&self) -> String {
+        let tag = match self.level {
+            LogLevel::Info => "INF",
+            LogLevel::Success => "SUC",
+            LogLevel::Warning => "WRN",
+            LogLevel::Error => "ERR",
+        };
+
+        if self.system { format!("APC_{tag}") } else { tag.to_string() }
+    }
+}
+
+pub trait LogFormatter {
+    fn get_extension(&self) -> &'static str;
+
+    fn format(&self, record: &LogRecord) -> String;
+}
+
+pub struct AnsiFormatter;
+
+impl LogFormatter for AnsiFormatter {
+    fn get_extension(&self) -> &'static str {
+        "log"
+    }
+
+    fn format(&self, record: &LogRecord) -> String {
+        let color = match record.level {
+            LogLevel::Info => "\x1b[37m",
+            LogLevel::Success => "\x1b[32m",
+            LogLevel::Warning => "\x1b[33m",
+            LogLevel::Error => "\x1b[31m",
+        };
+
+        format!("\x1b[90m{} [{}#{}]\x1b[0m {color}{}: {}\x1b[0m", record.timestamp, record.app_id, record.frame, record.get_tag(), record.message)
+    }
+}
+
+pub struct PlainFormatter;
+
+impl LogFormatter for PlainFormatter {
+    fn get_extension(&self) -> &'static str {
+        "log"
+    }
+
+    fn format(&self, record: &LogRecord) -> String {
+        format!("{} [{}#{}] {}: {}", record.timestamp, record.app_id, record.frame, record.get_tag(), record.message)
+    }
+}
+
+pub struct JsonLinesFormatter;
+
+impl LogFormatter for JsonLinesFormatter {
+    fn get_extension(&self) -> &'static str {
+        "jsonl"
+    }
+
+    fn format(&self, record: &LogRecord) -> String {
+        let level = match record.level {
+            LogLevel::Info => "info",
+            LogLevel::Success => "success",
+            LogLevel::Warning => "warning",
+            LogLevel::Error => "error",
+        };
+
+        format!(
+            "{{\"timestamp\":\"{}\",\"level\":\"{level}\",\"source\":\"{}\",\"app_id\":\"{}\",\"frame\":{},\"message\":\"{}\"}}",
+            escape_json(&record.timestamp),
+            if record.system { "system" } else { "app" },
+            escape_json(record.app_id),
+            record.frame,
+            escape_json(record.message),
+        )
+    }
+}
+
+pub fn create_formatter(format: LogFormat) -> Box<dyn LogFormatter> {
+    match format {
+        LogFormat::Ansi => Box::new(AnsiFormatter),
+        LogFormat::Plain => Box::new(PlainFormatter),
+        LogFormat::JsonLines => Box::new(JsonLinesFormatter),
+    }
+}
+
+fn escape_json(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+
+    escaped
+}
\ No newline at end of file
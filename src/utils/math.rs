@@ -0,0 +1,267 @@
+﻿//! Math builtins.
+//!
+//! Every integer type is computed as `i64` and every float type as `f64`. A function returns an
+//! `I64` when all of its arguments are integers and an `F64` as soon as one of them is a float.
+//! `Sqrt`, the trigonometric functions and `Lerp` always return an `F64`, while `Floor`, `Ceil`
+//! and `Round` always return an `I64`. `Pow` stays integral only for a non-negative integer
+//! exponent. Non-numeric arguments raise an `ArgumentError`, and results outside of the domain
+//! of a function (negative square root, overflow, ...) raise a `MathError`.
+
+use apica_common::bytecodes::ApicaBuiltinFunctionBytecode;
+use apica_common::element::{Element, ElementModifier};
+use apica_common::values::error::ValueError;
+use apica_common::values::f64::ValueF64;
+use apica_common::values::i64::ValueI64;
+use apica_common::values::value::Value;
+use std::cmp::Ordering;
+
+#[derive(Copy, Clone)]
+pub enum Number {
+    Integer(i64),
+    Float(f64),
+}
+
+impl Number {
+    pub fn from_element(element: &Element) -> Option<Number> {
+        match element.get_value() {
+            Value::U8(value) => value.get_value().map(|value| Number::Integer(value as i64)),
+            Value::U32(value) => value.get_value().map(|value| Number::Integer(value as i64)),
+            Value::U64(value) => value.get_value().map(|value| i64::try_from(value).map(Number::Integer).unwrap_or(Number::Float(value as f64))),
+            Value::I64(value) => value.get_value().map(Number::Integer),
+            Value::F64(value) => value.get_value().map(Number::Float),
+            _ => None,
+        }
+    }
+
+    pub fn as_f64(&self) -> f64 {
+        match self {
+            Number::Integer(integer) => *integer as f64,
+            Number::Float(float) => *float,
+        }
+    }
+
+    pub fn compare(&self, other: &Number) -> Option<Ordering> {
+        match (self, other) {
+            (Number::Integer(left), Number::Integer(right)) => Some(left.cmp(right)),
+            _ => self.as_f64().partial_cmp(&other.as_f64()),
+        }
+    }
+
+    pub fn to_element(self) -> Element {
+        match self {
+            Number::Integer(integer) => Element::init(ElementModifier::None, Value::I64(ValueI64::init_with(integer))),
+            Number::Float(float) => Element::init(ElementModifier::None, Value::F64(ValueF64::init_with(float))),
+        }
+    }
+}
+
+enum MathFailure {
+    Argument(String),
+    Domain(String),
+}
+
+fn numbers(name: &str, parameters: &[Element], count: usize) -> Result<Vec<Number>, MathFailure> {
+    if parameters.len() != count {
+        return Err(MathFailure::Argument(format!("The function `{name}` expects {count} argument(s) but received {}", parameters.len())));
+    }
+
+    let mut result = vec![];
+    for parameter in parameters {
+        match Number::from_element(parameter) {
+            Some(number) => result.push(number),
+            None => return Err(MathFailure::Argument(format!("The function `{name}` expects numeric arguments"))),
+        }
+    }
+
+    Ok(result)
+}
+
+fn checked_float(name: &str, value: f64) -> Result<Number, MathFailure> {
+    if value.is_finite() {
+        Ok(Number::Float(value))
+    } else {
+        Err(MathFailure::Domain(format!("The function `{name}` produced a non-finite result")))
+    }
+}
+
+fn float_to_integer(name: &str, value: f64) -> Result<Number, MathFailure> {
+    if value.is_finite() && value >= i64::MIN as f64 && value <= i64::MAX as f64 {
+        Ok(Number::Integer(value as i64))
+    } else {
+        Err(MathFailure::Domain(format!("The result of `{name}` does not fit in an I64")))
+    }
+}
+
+fn unary_float(name: &str, parameters: &[Element], operation: fn(f64) -> f64) -> Result<Number, MathFailure> {
+    let args = numbers(name, parameters, 1)?;
+    checked_float(name, operation(args[0].as_f64()))
+}
+
+fn rounding(name: &str, parameters: &[Element], operation: fn(f64) -> f64) -> Result<Number, MathFailure> {
+    match numbers(name, parameters, 1)?[0] {
+        Number::Integer(integer) => Ok(Number::Integer(integer)),
+        Number::Float(float) => float_to_integer(name, operation(float)),
+    }
+}
+
+fn compare(name: &str, parameters: &[Element], pick_left: fn(Ordering) -> bool) -> Result<Number, MathFailure> {
+    let args = numbers(name, parameters, 2)?;
+    let result = if args[0].compare(&args[1]).is_some_and(pick_left) { args[0] } else { args[1] };
+    match (args[0], args[1]) {
+        (Number::Integer(_), Number::Integer(_)) => Ok(result),
+        _ => Ok(Number::Float(result.as_f64())),
+    }
+}
+
+fn abs(parameters: &[Element]) -> Result<Number, MathFailure> {
+    match numbers("Abs", parameters, 1)?[0] {
+        Number::Integer(integer) => match integer.checked_abs() {
+            Some(value) => Ok(Number::Integer(value)),
+            None => Err(MathFailure::Domain(String::from("The result of `Abs` does not fit in an I64"))),
+        },
+        Number::Float(float) => Ok(Number::Float(float.abs())),
+    }
+}
+
+fn clamp(parameters: &[Element]) -> Result<Number, MathFailure> {
+    let args = numbers("Clamp", parameters, 3)?;
+    match args[1].compare(&args[2]) {
+        Some(Ordering::Greater) => return Err(MathFailure::Domain(String::from("The minimum of `Clamp` is greater than its maximum"))),
+        None => return Err(MathFailure::Domain(String::from("The bounds of `Clamp` must not be NaN"))),
+        _ => {},
+    }
+
+    match (args[0], args[1], args[2]) {
+        (Number::Integer(value), Number::Integer(min), Number::Integer(max)) => Ok(Number::Integer(value.clamp(min, max))),
+        _ => Ok(Number::Float(args[0].as_f64().clamp(args[1].as_f64(), args[2].as_f64()))),
+    }
+}
+
+fn pow(parameters: &[Element]) -> Result<Number, MathFailure> {
+    let args = numbers("Pow", parameters, 2)?;
+    if let (Number::Integer(base), Number::Integer(exponent)) = (args[0], args[1]) && exponent >= 0 {
+        return match u32::try_from(exponent).ok().and_then(|exponent| base.checked_pow(exponent)) {
+            Some(value) => Ok(Number::Integer(value)),
+            None => Err(MathFailure::Domain(String::from("The result of `Pow` does not fit in an I64"))),
+        };
+    }
+
+    checked_float("Pow", args[0].as_f64().powf(args[1].as_f64()))
+}
+
+fn sqrt(parameters: &[Element]) -> Result<Number, MathFailure> {
+    let args = numbers("Sqrt", parameters, 1)?;
+    if args[0].as_f64() < 0.0 {
+        return Err(MathFailure::Domain(String::from("Cannot compute the square root of a negative number")));
+    }
+
+    checked_float("Sqrt", args[0].as_f64().sqrt())
+}
+
+fn inverse_trigonometry(name: &str, parameters: &[Element], operation: fn(f64) -> f64) -> Result<Number, MathFailure> {
+    let args = numbers(name, parameters, 1)?;
+    if !(-1.0..=1.0).contains(&args[0].as_f64()) {
+        return Err(MathFailure::Domain(format!("The argument of `{name}` must be between -1 and 1")));
+    }
+
+    checked_float(name, operation(args[0].as_f64()))
+}
+
+fn atan2(parameters: &[Element]) -> Result<Number, MathFailure> {
+    let args = numbers("Atan2", parameters, 2)?;
+    checked_float("Atan2", args[0].as_f64().atan2(args[1].as_f64()))
+}
+
+fn lerp(parameters: &[Element]) -> Result<Number, MathFailure> {
+    let args = numbers("Lerp", parameters, 3)?;
+    let (start, end, t) = (args[0].as_f64(), args[1].as_f64(), args[2].as_f64());
+    checked_float("Lerp", start + (end - start) * t)
+}
+
+fn sign(parameters: &[Element]) -> Result<Number, MathFailure> {
+    match numbers("Sign", parameters, 1)?[0] {
+        Number::Integer(integer) => Ok(Number::Integer(integer.signum())),
+        Number::Float(float) if float == 0.0 || float.is_nan() => Ok(Number::Float(0.0)),
+        Number::Float(float) => Ok(Number::Float(float.signum())),
+    }
+}
+
+pub fn evaluate(function: &ApicaBuiltinFunctionBytecode, parameters: Vec<Element>) -> Element {
+    let result = match function {
+        ApicaBuiltinFunctionBytecode::Abs => abs(&parameters),
+        ApicaBuiltinFunctionBytecode::Min => compare("Min", &parameters, Ordering::is_le),
+        ApicaBuiltinFunctionBytecode::Max => compare("Max", &parameters, Ordering::is_ge),
+        ApicaBuiltinFunctionBytecode::Clamp => clamp(&parameters),
+        ApicaBuiltinFunctionBytecode::Pow => pow(&parameters),
+        ApicaBuiltinFunctionBytecode::Sqrt => sqrt(&parameters),
+        ApicaBuiltinFunctionBytecode::Floor => rounding("Floor", &parameters, f64::floor),
+        ApicaBuiltinFunctionBytecode::Ceil => rounding("Ceil", &parameters, f64::ceil),
+        ApicaBuiltinFunctionBytecode::Round => rounding("Round", &parameters, f64::round),
+        ApicaBuiltinFunctionBytecode::Sin => unary_float("Sin", &parameters, f64::sin),
+        ApicaBuiltinFunctionBytecode::Cos => unary_float("Cos", &parameters, f64::cos),
+        ApicaBuiltinFunctionBytecode::Tan => unary_float("Tan", &parameters, f64::tan),
+        ApicaBuiltinFunctionBytecode::Asin => inverse_trigonometry("Asin", &parameters, f64::asin),
+        ApicaBuiltinFunctionBytecode::Acos => inverse_trigonometry("Acos", &parameters, f64::acos),
+        ApicaBuiltinFunctionBytecode::Atan => unary_float("Atan", &parameters, f64::atan),
+        ApicaBuiltinFunctionBytecode::Atan2 => atan2(&parameters),
+        ApicaBuiltinFunctionBytecode::Lerp => lerp(&parameters),
+        ApicaBuiltinFunctionBytecode::Sign => sign(&parameters),
+
+        _ => Err(MathFailure::Argument(format!("Not a math function -> {:?}", function))),
+    };
+
+    match result {
+        Ok(number) => number.to_element(),
+        Err(MathFailure::Argument(details)) => Element::create_error(Value::Error(ValueError::init_with(
+            String::from("ArgumentError"),
+            Some(details)
+        ))),
+        Err(MathFailure::Domain(details)) => Element::create_error(Value::Error(ValueError::init_with(
+            String::from("MathError"),
+            Some(details)
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn float(value: f64) -> Element {
+        Element::init(ElementModifier::None, Value::F64(ValueF64::init_with(value)))
+    }
+
+    fn integer(value: i64) -> Element {
+        Element::init(ElementModifier::None, Value::I64(ValueI64::init_with(value)))
+    }
+
+    #[test]
+    fn integral_float_stays_a_float() {
+        assert!(matches!(Number::from_element(&float(3.0)), Some(Number::Float(value)) if value == 3.0));
+        assert!(matches!(Number::from_element(&integer(3)), Some(Number::Integer(3))));
+    }
+
+    #[test]
+    fn pow_with_a_float_argument_returns_a_float() {
+        let result = evaluate(&ApicaBuiltinFunctionBytecode::Pow, vec![float(2.0), integer(2)]);
+        assert!(matches!(result.get_value(), Value::F64(value) if value.get_value() == Some(4.0)));
+
+        let result = evaluate(&ApicaBuiltinFunctionBytecode::Pow, vec![integer(2), integer(2)]);
+        assert!(matches!(result.get_value(), Value::I64(value) if value.get_value() == Some(4)));
+    }
+
+    #[test]
+    fn large_integers_are_compared_exactly() {
+        let result = evaluate(&ApicaBuiltinFunctionBytecode::Min, vec![integer(i64::MAX), integer(i64::MAX - 1)]);
+        assert!(matches!(result.get_value(), Value::I64(value) if value.get_value() == Some(i64::MAX - 1)));
+
+        let result = evaluate(&ApicaBuiltinFunctionBytecode::Clamp, vec![integer(0), integer(i64::MAX), integer(i64::MAX - 1)]);
+        assert!(matches!(result.get_value(), Value::Error(_)));
+    }
+
+    #[test]
+    fn clamp_rejects_nan_bounds() {
+        let result = evaluate(&ApicaBuiltinFunctionBytecode::Clamp, vec![float(1.0), float(f64::NAN), float(2.0)]);
+        assert!(matches!(result.get_value(), Value::Error(_)));
+    }
+}
\ No newline at end of file
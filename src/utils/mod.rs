@@ -1,2 +1,8 @@
 ﻿pub mod read;
-pub mod rights;
\ No newline at end of file
+pub mod rights;
+pub mod format;
+pub mod math;
+pub mod config;
+pub mod dump;
+pub mod write;
+pub mod logging;
\ No newline at end of file
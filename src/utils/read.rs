@@ -1,6 +1,15 @@
 ﻿use std::fs::File;
 use std::io::Read;
 use apica_common::bytecodes::{ApicaBuiltinFunctionBytecode, ApicaBytecode, ApicaEntrypointBytecode, ApicaSpecificationBytecode, ApicaTypeBytecode};
+use apica_common::values::bool::ValueBool;
+use apica_common::values::f64::ValueF64;
+use apica_common::values::i64::ValueI64;
+use apica_common::values::null::ValueNull;
+use apica_common::values::string::ValueString;
+use apica_common::values::u32::ValueU32;
+use apica_common::values::u64::ValueU64;
+use apica_common::values::u8::ValueU8;
+use apica_common::values::value::Value;
 
 pub fn read_u8(file: &mut File) -> Option<u8> {
     let mut buffer = [0u8; 1];
@@ -110,4 +119,28 @@ pub fn read_specification_bytecode(file: &mut File) -> Option<ApicaSpecification
     } else {
         None
     }
+}
+
+fn read_optional<T>(file: &mut File, read_payload: fn(&mut File) -> Option<T>) -> Option<Option<T>> {
+    match read_u8(file)? {
+        0 => Some(None),
+        _ => read_payload(file).map(Some),
+    }
+}
+
+pub fn read_value(file: &mut File) -> Option<Value> {
+    match read_type_bytecode(file)? {
+        ApicaTypeBytecode::Null => Some(Value::Null(ValueNull::init())),
+        ApicaTypeBytecode::Bool => Some(Value::Bool(read_optional(file, |file| read_u8(file).map(|value| value != 0))?
+            .map_or(ValueBool::init(), ValueBool::init_with))),
+        ApicaTypeBytecode::U8 => Some(Value::U8(read_optional(file, read_u8)?.map_or(ValueU8::init(), ValueU8::init_with))),
+        ApicaTypeBytecode::U32 => Some(Value::U32(read_optional(file, read_u32)?.map_or(ValueU32::init(), ValueU32::init_with))),
+        ApicaTypeBytecode::U64 => Some(Value::U64(read_optional(file, read_u64)?.map_or(ValueU64::init(), ValueU64::init_with))),
+        ApicaTypeBytecode::I64 => Some(Value::I64(read_optional(file, |file| read_u64(file).map(|value| value as i64))?
+            .map_or(ValueI64::init(), ValueI64::init_with))),
+        ApicaTypeBytecode::F64 => Some(Value::F64(read_optional(file, |file| read_u64(file).map(f64::from_bits))?
+            .map_or(ValueF64::init(), ValueF64::init_with))),
+        ApicaTypeBytecode::String => Some(Value::String(read_optional(file, read_string)?.map_or(ValueString::init(), ValueString::init_with))),
+        _ => None,
+    }
 }
\ No newline at end of file
@@ -1,6 +1,8 @@
 ﻿use bitflags::bitflags;
+use crate::utils::config::SystemConfig;
 
 bitflags! {
+    #[derive(Copy, Clone)]
     pub struct ApicaRight : u8 {
         const MainMenuRight =   0b0000_0100;
         const AppRight =        0b0000_0010;
@@ -12,6 +14,47 @@ bitflags! {
     }
 }
 
+bitflags! {
+    #[derive(Copy, Clone, PartialEq)]
+    pub struct ApicaCapability : u32 {
+        const Window =          0b0000_0001;
+        const FileAccess =      0b0000_0010;
+        const Storage =         0b0000_0100;
+        const Network =         0b0000_1000;
+        const LaunchApps =      0b0001_0000;
+        const Quit =            0b0010_0000;
+
+        const Default =         0b0010_0001;
+    }
+}
+
+pub const APICA_CAPABILITIES: [(&str, ApicaCapability); 6] = [
+    ("window", ApicaCapability::Window),
+    ("file_access", ApicaCapability::FileAccess),
+    ("storage", ApicaCapability::Storage),
+    ("network", ApicaCapability::Network),
+    ("launch_apps", ApicaCapability::LaunchApps),
+    ("quit", ApicaCapability::Quit),
+];
+
+pub fn get_capability_name(capability: ApicaCapability) -> &'static str {
+    APICA_CAPABILITIES.iter().find(|(_, other)| *other == capability).map(|(name, _)| *name).unwrap_or("unknown")
+}
+
+pub fn apply_policy(policy: &SystemConfig, app_id: &str, requested: ApicaCapability) -> ApicaCapability {
+    let mut capabilities = requested;
+    for (name, capability) in APICA_CAPABILITIES {
+        let rule = policy.get_string(&format!("{app_id}.{name}")).or_else(|| policy.get_string(&format!("*.{name}")));
+        match rule.map(|rule| rule.as_str()) {
+            Some("allow") => capabilities |= capability,
+            Some("deny") => capabilities -= capability,
+            _ => {},
+        }
+    }
+
+    capabilities
+}
+
 #[derive(PartialEq)]
 pub enum ApicaMode {
     SpecialQuit, SpecialInit,
@@ -19,4 +62,44 @@ pub enum ApicaMode {
     Init, Update, Quit
 }
 
-pub const APICA_MAIN_MENU: &str = "APICA_MENU";
\ No newline at end of file
+pub const APICA_MAIN_MENU: &str = "APICA_MENU";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const POLICY: &str = "
+        # Every app may use the storage, but no app may reach the network.
+        *.storage = allow
+        *.network = deny
+
+        editor.network = allow
+        editor.quit = deny
+        kiosk.storage = deny
+        kiosk.window = maybe
+    ";
+
+    #[test]
+    fn wildcard_rules_apply_to_every_app() {
+        let policy = SystemConfig::parse(POLICY);
+        let granted = apply_policy(&policy, "game", ApicaCapability::Default | ApicaCapability::Network);
+        assert!(granted == ApicaCapability::Default | ApicaCapability::Storage);
+    }
+
+    #[test]
+    fn app_rules_take_precedence_over_wildcards() {
+        let policy = SystemConfig::parse(POLICY);
+        let granted = apply_policy(&policy, "editor", ApicaCapability::Default);
+        assert!(granted == ApicaCapability::Window | ApicaCapability::Storage | ApicaCapability::Network);
+
+        let granted = apply_policy(&policy, "kiosk", ApicaCapability::Default | ApicaCapability::Storage);
+        assert!(granted == ApicaCapability::Default);
+    }
+
+    #[test]
+    fn unknown_rules_keep_the_request() {
+        let granted = apply_policy(&SystemConfig::parse("kiosk.window = maybe"), "kiosk", ApicaCapability::Window);
+        assert!(granted == ApicaCapability::Window);
+        assert_eq!(get_capability_name(ApicaCapability::FileAccess), "file_access");
+    }
+}
\ No newline at end of file
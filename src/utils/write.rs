@@ -0,0 +1,68 @@
+﻿use std::fs::{rename, File};
+use std::io::Write;
+use apica_common::bytecodes::ApicaTypeBytecode;
+use apica_common::values::value::Value;
+
+pub fn write_u8(output: &mut impl Write, value: u8) -> bool {
+    output.write_all(&[value]).is_ok()
+}
+
+pub fn write_u16(output: &mut impl Write, value: u16) -> bool {
+    output.write_all(&value.to_le_bytes()).is_ok()
+}
+
+pub fn write_u32(output: &mut impl Write, value: u32) -> bool {
+    output.write_all(&value.to_le_bytes()).is_ok()
+}
+
+pub fn write_u64(output: &mut impl Write, value: u64) -> bool {
+    output.write_all(&value.to_le_bytes()).is_ok()
+}
+
+// Strings are NUL-terminated, so a string that contains a NUL cannot be written.
+pub fn write_string(output: &mut impl Write, value: &str) -> bool {
+    !value.contains('\0') && output.write_all(value.as_bytes()).is_ok() && write_u8(output, 0)
+}
+
+pub fn write_type_bytecode(output: &mut impl Write, bytecode: ApicaTypeBytecode) -> bool {
+    write_u64(output, bytecode as u64)
+}
+
+// Every non-null value is prefixed by a presence byte, so that a typed null survives a round trip.
+fn write_optional<W: Write, T>(output: &mut W, value: Option<T>, write_payload: impl FnOnce(&mut W, T) -> bool) -> bool {
+    match value {
+        Some(value) => write_u8(output, 1) && write_payload(output, value),
+        None => write_u8(output, 0),
+    }
+}
+
+pub fn write_value(output: &mut impl Write, value: &Value) -> bool {
+    match value {
+        Value::Null(_) => write_type_bytecode(output, ApicaTypeBytecode::Null),
+        Value::Bool(boolean) => write_type_bytecode(output, ApicaTypeBytecode::Bool)
+            && write_optional(output, boolean.get_value(), |output, value| write_u8(output, value as u8)),
+        Value::U8(integer) => write_type_bytecode(output, ApicaTypeBytecode::U8)
+            && write_optional(output, integer.get_value(), |output, value| write_u8(output, value)),
+        Value::U32(integer) => write_type_bytecode(output, ApicaTypeBytecode::U32)
+            && write_optional(output, integer.get_value(), |output, value| write_u32(output, value)),
+        Value::U64(integer) => write_type_bytecode(output, ApicaTypeBytecode::U64)
+            && write_optional(output, integer.get_value(), |output, value| write_u64(output, value)),
+        Value::I64(integer) => write_type_bytecode(output, ApicaTypeBytecode::I64)
+            && write_optional(output, integer.get_value(), |output, value| write_u64(output, value as u64)),
+        Value::F64(float) => write_type_bytecode(output, ApicaTypeBytecode::F64)
+            && write_optional(output, float.get_value(), |output, value| write_u64(output, value.to_bits())),
+        Value::String(string) => write_type_bytecode(output, ApicaTypeBytecode::String)
+            && write_optional(output, string.get_value().as_deref(), |output, value| write_string(output, value)),
+        _ => false,
+    }
+}
+
+pub fn write_file_atomically(filepath: &str, content: &[u8]) -> bool {
+    let temporary_filepath = format!("{filepath}.tmp");
+    let written = match File::create(&temporary_filepath) {
+        Ok(mut file) => file.write_all(content).is_ok() && file.sync_all().is_ok(),
+        Err(_) => false,
+    };
+
+    written && rename(&temporary_filepath, filepath).is_ok()
+}
\ No newline at end of file